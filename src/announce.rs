@@ -0,0 +1,101 @@
+use crate::guess::{CharState, GuessResult};
+use crate::keyboard_view::KeyboardView;
+use crate::knowledge::Knowledge;
+
+/// Word read out for the state of a letter, which does not rely on colour.
+pub fn state_word(state: CharState) -> &'static str {
+    match state {
+        CharState::CorrectPosition => "correct",
+        CharState::IncorrectPosition => "elsewhere",
+        CharState::NotFound => "absent",
+    }
+}
+
+/// A guess read out letter by letter, e.g.
+/// "C correct, L elsewhere, O elsewhere, N elsewhere, E absent".
+pub fn describe_guess(guess_result: &GuessResult) -> String {
+    guess_result
+        .char_guesses
+        .iter()
+        .map(|&(ch, state)| format!("{} {}", ch.to_uppercase(), state_word(state)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Letters of the alphabet by what is known of them on the keyboard: one
+/// list each for the correct, elsewhere, absent and untried letters, in
+/// alphabetical order. Empty lists are left out.
+pub fn letter_lists(keyboard: &KeyboardView) -> Vec<(&'static str, Vec<char>)> {
+    let groups = [
+        ("correct", Some(CharState::CorrectPosition)),
+        ("elsewhere", Some(CharState::IncorrectPosition)),
+        ("absent", Some(CharState::NotFound)),
+        ("untried", None),
+    ];
+    groups
+        .into_iter()
+        .map(|(name, state)| {
+            let letters = ('a'..='z').filter(|&ch| keyboard.get(ch) == state);
+            (name, letters.collect::<Vec<_>>())
+        })
+        .filter(|(_, letters)| !letters.is_empty())
+        .collect()
+}
+
+/// Letters known to be at each position, e.g. "1 C, 3 A", or None if no
+/// position is known yet.
+pub fn describe_known_positions(knowledge: &Knowledge) -> Option<String> {
+    let known: Vec<String> = (0..knowledge.word_length())
+        .filter_map(|pos| {
+            knowledge
+                .fixed(pos)
+                .map(|ch| format!("{} {}", pos + 1, ch.to_uppercase()))
+        })
+        .collect();
+    if known.is_empty() {
+        None
+    } else {
+        Some(known.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess::compute_guess_result;
+
+    #[test]
+    fn test_describe_guess() {
+        assert_eq!(
+            "C correct, L elsewhere, O elsewhere, N elsewhere, E absent",
+            describe_guess(&compute_guess_result("clone", "colon"))
+        );
+    }
+
+    #[test]
+    fn test_letter_lists() {
+        let mut keyboard = KeyboardView::new();
+        keyboard.record_guess_result(&compute_guess_result("clone", "colon"));
+        let lists = letter_lists(&keyboard);
+        assert_eq!(("correct", vec!['c']), lists[0]);
+        assert_eq!(("elsewhere", vec!['l', 'n', 'o']), lists[1]);
+        assert_eq!(("absent", vec!['e']), lists[2]);
+        assert_eq!("untried", lists[3].0);
+        assert_eq!(21, lists[3].1.len());
+
+        let lists = letter_lists(&KeyboardView::new());
+        assert_eq!(1, lists.len());
+        assert_eq!(26, lists[0].1.len());
+    }
+
+    #[test]
+    fn test_describe_known_positions() {
+        let mut knowledge = Knowledge::new(5);
+        assert_eq!(None, describe_known_positions(&knowledge));
+        knowledge.record(&compute_guess_result("crane", "crate"));
+        assert_eq!(
+            Some("1 C, 2 R, 3 A, 5 E".to_string()),
+            describe_known_positions(&knowledge)
+        );
+    }
+}
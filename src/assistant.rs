@@ -0,0 +1,245 @@
+use crate::board::Board;
+use crate::dictionary::Dictionary;
+use crate::guess::GuessResult;
+use crate::guess::PatternError;
+use crate::keyboard_view::KeyboardView;
+use crate::solver::Solver;
+use std::fmt;
+
+/// Helps solve a wordle puzzle played elsewhere, where the target is unknown.
+/// The user reports each guess along with the colour pattern it received,
+/// and the assistant narrows down the words that may still be the target.
+pub struct Assistant {
+    /// picks the next guess from all the words of the dictionary
+    solver: Solver,
+
+    /// length of every word in the dictionary
+    word_length: usize,
+
+    /// indexes of the words that are consistent with every guess so far
+    candidates: Vec<usize>,
+
+    /// candidates before each guess was recorded, used to undo guesses
+    previous_candidates: Vec<Vec<usize>>,
+
+    /// state of the keyboard, given the guesses recorded so far
+    pub keyboard_view: KeyboardView,
+
+    /// results of each guess in order of submission
+    pub guess_results: Vec<GuessResult>,
+}
+
+/// Reasons why a guess could not be recorded by the assistant.
+#[derive(Debug, PartialEq)]
+pub enum AssistantError {
+    /// input is not of the form "<word> <pattern>"
+    MalformedInput,
+    /// guessed word does not have the same length as the dictionary words
+    WrongLength(String),
+    /// colour pattern could not be parsed
+    InvalidPattern(PatternError),
+    /// no word in the dictionary is consistent with all the guesses
+    Inconsistent(GuessResult),
+}
+
+impl fmt::Display for AssistantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssistantError::MalformedInput => {
+                write!(f, "enter a guess followed by its colours, e.g. crane bgybb")
+            }
+            AssistantError::WrongLength(word) => {
+                write!(f, "{word} does not have the right number of letters")
+            }
+            AssistantError::InvalidPattern(err) => write!(f, "{err}"),
+            AssistantError::Inconsistent(guess_result) => write!(
+                f,
+                "no word matches {} with the earlier guesses, check for a typo",
+                guess_result.word()
+            ),
+        }
+    }
+}
+
+impl Assistant {
+    /// Create a new assistant where every word of the dictionary may be the target.
+    pub fn new(dict: &Dictionary) -> Assistant {
+        Assistant::with_solver(Solver::new(dict.sorted_words()), dict.word_length)
+    }
+
+    /// Create a new assistant using a solver that was set up already, e.g.
+    /// one backed by a precomputed pattern matrix.
+    pub fn with_solver(solver: Solver, word_length: usize) -> Assistant {
+        Assistant {
+            candidates: solver.all_candidates(),
+            solver,
+            word_length,
+            previous_candidates: vec![],
+            keyboard_view: KeyboardView::new(),
+            guess_results: vec![],
+        }
+    }
+
+    /// Record a line of user input of the form "crane bgybb".
+    pub fn record_input(&mut self, input: &str) -> Result<&GuessResult, AssistantError> {
+        let mut parts = input.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(word), Some(pattern), None) => self.record(word, pattern),
+            _ => Err(AssistantError::MalformedInput),
+        }
+    }
+
+    /// Record a guess and the colour pattern it received.
+    /// The guess is rejected if it leaves no candidates, since that means
+    /// either this or an earlier pattern was entered incorrectly.
+    pub fn record(&mut self, word: &str, pattern: &str) -> Result<&GuessResult, AssistantError> {
+        let word = word.to_lowercase();
+        if word.chars().count() != self.word_length {
+            return Err(AssistantError::WrongLength(word));
+        }
+        let guess_result =
+            GuessResult::from_pattern(&word, pattern).map_err(AssistantError::InvalidPattern)?;
+
+        let remaining = self.solver.filter(&self.candidates, &guess_result);
+        if remaining.is_empty() {
+            return Err(AssistantError::Inconsistent(guess_result));
+        }
+
+        self.keyboard_view.record_guess_result(&guess_result);
+        let previous = std::mem::replace(&mut self.candidates, remaining);
+        self.previous_candidates.push(previous);
+        self.guess_results.push(guess_result);
+        Ok(self.guess_results.last().unwrap())
+    }
+
+    /// Undo the last recorded guess. Returns the removed guess, if any.
+    pub fn undo(&mut self) -> Option<GuessResult> {
+        let guess_result = self.guess_results.pop()?;
+        // safe to unwrap, candidates are saved for every recorded guess
+        self.candidates = self.previous_candidates.pop().unwrap();
+
+        // the keyboard view only aggregates, so rebuild it from the remaining guesses
+        self.keyboard_view = KeyboardView::new();
+        for gr in self.guess_results.iter() {
+            self.keyboard_view.record_guess_result(gr);
+        }
+        Some(guess_result)
+    }
+
+    /// Words that are consistent with all the guesses so far.
+    pub fn candidates(&self) -> Vec<&str> {
+        let words = self.solver.words();
+        self.candidates.iter().map(|&i| words[i].as_str()).collect()
+    }
+
+    /// Suggest the next guess. Returns None if there are no candidates left.
+    pub fn suggest(&self) -> Option<String> {
+        self.solver
+            .best_guess(&self.candidates)
+            .map(|i| self.solver.words()[i].to_string())
+    }
+
+    /// Has the puzzle been solved, i.e. was the last guess all green?
+    pub fn is_solved(&self) -> bool {
+        self.guess_results.last().is_some_and(|gr| gr.is_correct())
+    }
+}
+
+impl Board for Assistant {
+    fn guess_results(&self) -> &[GuessResult] {
+        &self.guess_results
+    }
+
+    fn keyboard_view(&self) -> &KeyboardView {
+        &self.keyboard_view
+    }
+
+    fn word_length(&self) -> usize {
+        self.word_length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess::CharState;
+
+    fn big_dict() -> Dictionary {
+        let mut dict = Dictionary::new(5);
+        dict.add_word_str("clone");
+        dict.add_word_str("colon");
+        dict.add_word_str("spoon");
+        dict.add_word_str("ovolo");
+        dict.add_word_str("potoo");
+        dict.add_word_str("other");
+        dict.add_word_str("siena");
+        dict
+    }
+
+    #[test]
+    fn test_new_assistant() {
+        let assistant = Assistant::new(&big_dict());
+        assert_eq!(7, assistant.candidates().len());
+        assert!(assistant.suggest().is_some());
+        assert!(!assistant.is_solved());
+    }
+
+    #[test]
+    fn test_record_narrows_candidates() {
+        let mut assistant = Assistant::new(&big_dict());
+        assistant.record_input("spoon bbygg").unwrap();
+        assert_eq!(vec!["colon"], assistant.candidates());
+        assert_eq!(Some("colon".to_string()), assistant.suggest());
+        assert_eq!(
+            Some(CharState::CorrectPosition),
+            assistant.keyboard_view.get('n')
+        );
+
+        assistant.record_input("COLON ggggg").unwrap();
+        assert!(assistant.is_solved());
+    }
+
+    #[test]
+    fn test_record_invalid_input() {
+        let mut assistant = Assistant::new(&big_dict());
+        assert_eq!(
+            Err(AssistantError::MalformedInput),
+            assistant.record_input("spoon")
+        );
+        assert_eq!(
+            Err(AssistantError::WrongLength("spoons".to_string())),
+            assistant.record_input("spoons bbbbbb")
+        );
+        assert_eq!(
+            Err(AssistantError::InvalidPattern(PatternError::InvalidCode(
+                'x'
+            ))),
+            assistant.record_input("spoon bbbbx")
+        );
+        assert!(assistant.guess_results.is_empty());
+    }
+
+    #[test]
+    fn test_record_inconsistent() {
+        let mut assistant = Assistant::new(&big_dict());
+        assistant.record_input("spoon bbygg").unwrap();
+
+        let err = assistant.record_input("clone ggggg").unwrap_err();
+        assert!(matches!(err, AssistantError::Inconsistent(_)));
+        assert_eq!(1, assistant.guess_results.len());
+        assert_eq!(1, assistant.candidates().len());
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut assistant = Assistant::new(&big_dict());
+        assert_eq!(None, assistant.undo());
+
+        assistant.record_input("spoon bbygg").unwrap();
+        let undone = assistant.undo().unwrap();
+        assert_eq!("spoon", undone.word());
+        assert_eq!(7, assistant.candidates().len());
+        assert!(assistant.guess_results.is_empty());
+        assert_eq!(None, assistant.keyboard_view.get('s'));
+    }
+}
@@ -0,0 +1,282 @@
+use std::io::{self, BufRead, Write};
+use wordle_star::announce;
+use wordle_star::board::Board;
+use wordle_star::game::{GameState, GuessError, SubmitError};
+
+use crate::clock::{self, Clock};
+use crate::Session;
+
+/// Commands that may be typed instead of a guess.
+const COMMANDS: &str = "/letters lists the letters by what is known of them, \
+    /board reads the guesses so far, /give-up reveals the word, \
+    /new starts the next game once this one is over, and /quit stops.";
+
+/// Play in lines of plain text, without colours or cursor movement, so
+/// that a screen reader reads out everything as it is printed. Guesses are
+/// read from stdin, one per line, until it ends or /quit is typed.
+pub fn run(session: &Session) -> io::Result<()> {
+    play(session, io::stdin().lock(), &mut io::stdout())
+}
+
+fn play(session: &Session, input: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "Wordle Star, accessible mode.")?;
+    introduce(session, out)?;
+    prompt(session, out)?;
+    for line in input.lines() {
+        let line = line?.trim().to_lowercase();
+        match line.as_str() {
+            "" => (),
+            "/help" => {
+                introduce(session, out)?;
+                writeln!(out, "{COMMANDS}")?;
+            }
+            "/letters" => letters(session, out)?,
+            "/board" => board(session, out)?,
+            "/give-up" => give_up(session, out)?,
+            "/new" => new_game(session, out)?,
+            "/quit" => break,
+            command if command.starts_with('/') => {
+                writeln!(out, "Unknown command {command}. Type /help for commands.")?;
+            }
+            guess => submit(session, guess, out)?,
+        }
+        prompt(session, out)?;
+    }
+    Ok(())
+}
+
+/// The rules of the game.
+fn introduce(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    writeln!(
+        out,
+        "Guess the {} letter word in {} tries. After each guess, every letter \
+         is read out as correct, elsewhere in the word, or absent.",
+        game.word_length(),
+        game.max_guesses()
+    )?;
+    if game.hard_mode() {
+        writeln!(
+            out,
+            "Hard mode: letters that were revealed must be used again."
+        )?;
+    }
+    if let Clock::Countdown(limit) = session.clock {
+        writeln!(
+            out,
+            "Each game must be solved within {}.",
+            clock::format_duration(limit)
+        )?;
+    }
+    writeln!(out, "Type a guess and press enter, or /help for commands.")
+}
+
+/// Ask for the next guess, or for a command once the game is over.
+fn prompt(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    if game.state == GameState::Playing {
+        write!(
+            out,
+            "Guess {} of {}: ",
+            game.guess_results().len() + 1,
+            game.max_guesses()
+        )?;
+    } else {
+        write!(out, "Type /new or /quit: ")?;
+    }
+    out.flush()
+}
+
+fn submit(session: &Session, guess: &str, out: &mut impl Write) -> io::Result<()> {
+    let word = guess.to_uppercase();
+    let submitted = session.game.borrow_mut().submit(guess);
+    let result = match submitted {
+        Ok(result) => result,
+        Err(SubmitError::GameOver) => {
+            return writeln!(out, "The game is over. Type /new to play again.")
+        }
+        Err(SubmitError::OutOfTime) => return finish(session, out),
+        Err(SubmitError::WrongLength { letters, expected }) => {
+            return writeln!(
+                out,
+                "{word} has {letters} letters, guesses have {expected}."
+            )
+        }
+        Err(SubmitError::Invalid(GuessError::NotInDictionary)) => {
+            return writeln!(out, "{word} is not in the word list.")
+        }
+        Err(SubmitError::Invalid(err)) => return writeln!(out, "Hard mode: {err}."),
+    };
+    let game = session.game.borrow();
+    writeln!(
+        out,
+        "Guess {}, {word}: {}.",
+        game.guess_results().len(),
+        announce::describe_guess(&result)
+    )?;
+    if game.state == GameState::Playing {
+        let left = game.max_guesses() - game.guess_results().len();
+        writeln!(out, "{left} {} left.", plural(left, "guess", "guesses"))?;
+        return Ok(());
+    }
+    drop(game);
+    finish(session, out)
+}
+
+/// Say how the game ended, and record it in the stats.
+fn finish(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    let target = game.target_word.to_uppercase();
+    match game.state {
+        GameState::Win => {
+            let guesses = game.guess_results().len();
+            writeln!(
+                out,
+                "Solved in {guesses} {}! The word was {target}.",
+                plural(guesses, "guess", "guesses")
+            )?;
+        }
+        GameState::Lose => writeln!(out, "You lost. The word was {target}.")?,
+        GameState::OutOfTime => writeln!(out, "Out of time. The word was {target}.")?,
+        GameState::Playing => return Ok(()),
+    }
+    if session.clock != Clock::Off {
+        writeln!(out, "Time: {}.", clock::format_duration(game.duration()))?;
+    }
+    if let Err(err) = session.record_game() {
+        eprintln!("could not save statistics: {err}");
+    }
+    writeln!(out, "Type /new to play again, or /quit to stop.")
+}
+
+/// Every letter of the alphabet, in lists by what is known of it, and the
+/// letters known to be in place.
+fn letters(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    for (name, letters) in announce::letter_lists(game.keyboard_view()) {
+        let letters: Vec<String> = letters
+            .iter()
+            .map(|ch| ch.to_uppercase().to_string())
+            .collect();
+        writeln!(out, "{name}: {}.", letters.join(", "))?;
+    }
+    if let Some(known) = announce::describe_known_positions(&game.knowledge()) {
+        writeln!(out, "Known positions: {known}.")?;
+    }
+    Ok(())
+}
+
+/// Read out every guess so far.
+fn board(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    if game.guess_results().is_empty() {
+        return writeln!(out, "No guesses yet.");
+    }
+    for (index, result) in game.guess_results().iter().enumerate() {
+        writeln!(
+            out,
+            "Guess {}, {}: {}.",
+            index + 1,
+            result.word().to_uppercase(),
+            announce::describe_guess(result)
+        )?;
+    }
+    Ok(())
+}
+
+/// Lose the game, unless its time ran out already.
+fn give_up(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let mut game = session.game.borrow_mut();
+    if game.state != GameState::Playing {
+        return writeln!(out, "The game is already over.");
+    }
+    if !game.check_time() {
+        game.give_up();
+    }
+    drop(game);
+    finish(session, out)
+}
+
+fn new_game(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    if session.game.borrow().state == GameState::Playing {
+        return writeln!(out, "Finish this game first, or type /give-up.");
+    }
+    session.next_round();
+    writeln!(out, "New game.")?;
+    introduce(session, out)
+}
+
+fn plural(count: usize, one: &'static str, many: &'static str) -> &'static str {
+    if count == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::session;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    fn play_lines(lines: &str) -> String {
+        let mut out = vec![];
+        play(&session(), Cursor::new(lines), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_announce_guesses() {
+        let out = play_lines("tab\nca\ncow\nrat\n");
+        assert!(out.contains(
+            "Guess 1 of 3: Guess 1, TAB: T elsewhere, A correct, B absent.\n2 guesses left.\n"
+        ));
+        assert!(out.contains("CA has 2 letters, guesses have 3.\n"));
+        assert!(out.contains("COW is not in the word list.\n"));
+        assert!(out.contains("Guess 2, RAT: R absent, A correct, T correct.\n1 guess left.\n"));
+        assert!(out.ends_with("Guess 3 of 3: "));
+    }
+
+    #[test]
+    fn test_letters() {
+        let out = play_lines("tab\n/letters\n");
+        assert!(out.contains("correct: A.\nelsewhere: T.\nabsent: B.\nuntried: C, D, E,"));
+        assert!(out.contains("Known positions: 2 A.\n"));
+    }
+
+    #[test]
+    fn test_give_up_and_new_game() {
+        let out = play_lines("/new\n/give-up\n/give-up\ncat\n/new\ncat\n");
+        let expected = [
+            "Finish this game first, or type /give-up.",
+            "You lost. The word was CAT.",
+            "The game is already over.",
+            "The game is over. Type /new to play again.",
+            "New game.",
+            "Solved in 1 guess! The word was CAT.",
+        ];
+        let mut rest = out.as_str();
+        for line in expected {
+            let found = rest
+                .find(line)
+                .unwrap_or_else(|| panic!("{line:?} not in {rest:?}"));
+            rest = &rest[found + line.len()..];
+        }
+        assert!(rest.ends_with("Type /new or /quit: "));
+    }
+
+    #[test]
+    fn test_give_up_out_of_time() {
+        let session = session();
+        session
+            .game
+            .borrow_mut()
+            .set_time_limit(Some(Duration::ZERO));
+        let mut out = vec![];
+        play(&session, Cursor::new("/give-up\n"), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Out of time. The word was CAT.\n"));
+    }
+}
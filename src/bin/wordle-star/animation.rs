@@ -0,0 +1,191 @@
+use std::time::{Duration, Instant};
+
+/// Time each tile of a guess takes to flip over, one after the other.
+const FLIP: Duration = Duration::from_millis(250);
+
+/// Time the row of a guess that is not accepted shakes for, and each
+/// step of the shake.
+const SHAKE: Duration = Duration::from_millis(480);
+const SHAKE_STEP: Duration = Duration::from_millis(60);
+
+/// Time each tile of the winning row is in the air for. The next tile
+/// jumps when the previous one is half way.
+const BOUNCE: Duration = Duration::from_millis(200);
+
+/// What happens to a row of the grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Motion {
+    /// tiles flip one after another to show their colour
+    Reveal,
+    /// the row moves from side to side, for a guess that is not accepted
+    Shake,
+    /// tiles jump one after another, for the winning guess
+    Bounce,
+}
+
+/// How far a tile that is being revealed has turned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flip {
+    /// not turned yet, drawn like a letter being typed
+    Front,
+    /// half way, seen from the side
+    Edge,
+    /// turned, drawn in the colour of its state
+    Back,
+}
+
+/// An animation of a row of the grid, which starts at a point in time,
+/// possibly after another one.
+#[derive(Clone, Copy, Debug)]
+pub struct Animation {
+    pub motion: Motion,
+    pub row: usize,
+
+    /// number of tiles of the row
+    tiles: usize,
+    start: Instant,
+}
+
+impl Animation {
+    pub fn new(motion: Motion, row: usize, tiles: usize, start: Instant) -> Animation {
+        Animation {
+            motion,
+            row,
+            tiles,
+            start,
+        }
+    }
+
+    pub fn end(&self) -> Instant {
+        let duration = match self.motion {
+            Motion::Reveal => FLIP * self.tiles as u32,
+            Motion::Shake => SHAKE,
+            Motion::Bounce => BOUNCE / 2 * (self.tiles as u32 + 1),
+        };
+        self.start + duration
+    }
+
+    pub fn is_over(&self, now: Instant) -> bool {
+        now >= self.end()
+    }
+
+    /// Whether the animation is going on, and not waiting for its start.
+    pub fn is_running(&self, now: Instant) -> bool {
+        now >= self.start && !self.is_over(now)
+    }
+
+    /// How far a tile being revealed has turned. Tiles of other animations
+    /// are always turned.
+    pub fn flip(&self, col: usize, now: Instant) -> Flip {
+        if self.motion != Motion::Reveal || self.is_over(now) {
+            return Flip::Back;
+        }
+        let turned = now
+            .saturating_duration_since(self.start)
+            .checked_sub(FLIP * col as u32);
+        match turned {
+            Some(turned) if turned >= FLIP * 3 / 5 => Flip::Back,
+            Some(turned) if turned >= FLIP * 2 / 5 => Flip::Edge,
+            _ => Flip::Front,
+        }
+    }
+
+    /// Columns the row is moved by, to the right, or to the left if
+    /// negative.
+    pub fn shift(&self, now: Instant) -> isize {
+        if self.motion != Motion::Shake || !self.is_running(now) {
+            return 0;
+        }
+        let step = now.saturating_duration_since(self.start).as_millis() / SHAKE_STEP.as_millis();
+        if step.is_multiple_of(2) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Whether a tile of the row is in the air.
+    pub fn lifted(&self, col: usize, now: Instant) -> bool {
+        if self.motion != Motion::Bounce || !self.is_running(now) {
+            return false;
+        }
+        let jump = self.start + BOUNCE / 2 * col as u32;
+        now >= jump && now < jump + BOUNCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_end() {
+        let start = Instant::now();
+        let reveal = Animation::new(Motion::Reveal, 0, 5, start);
+        assert_eq!(start + ms(1250), reveal.end());
+        assert!(!reveal.is_over(start + ms(1249)));
+        assert!(reveal.is_over(start + ms(1250)));
+        assert_eq!(
+            start + ms(480),
+            Animation::new(Motion::Shake, 0, 5, start).end()
+        );
+        assert_eq!(
+            start + ms(600),
+            Animation::new(Motion::Bounce, 0, 5, start).end()
+        );
+    }
+
+    #[test]
+    fn test_flip() {
+        let start = Instant::now();
+        let reveal = Animation::new(Motion::Reveal, 0, 5, start);
+        assert_eq!(Flip::Front, reveal.flip(0, start));
+        assert_eq!(Flip::Edge, reveal.flip(0, start + ms(100)));
+        assert_eq!(Flip::Back, reveal.flip(0, start + ms(150)));
+        // the second tile turns once the first one is done
+        assert_eq!(Flip::Front, reveal.flip(1, start + ms(300)));
+        assert_eq!(Flip::Edge, reveal.flip(1, start + ms(350)));
+        assert_eq!(Flip::Front, reveal.flip(4, start + ms(1000)));
+        assert_eq!(Flip::Back, reveal.flip(4, start + ms(1250)));
+
+        let shake = Animation::new(Motion::Shake, 0, 5, start);
+        assert_eq!(Flip::Back, shake.flip(0, start));
+    }
+
+    #[test]
+    fn test_shift() {
+        let start = Instant::now();
+        let shake = Animation::new(Motion::Shake, 0, 5, start);
+        assert_eq!(1, shake.shift(start));
+        assert_eq!(-1, shake.shift(start + ms(60)));
+        assert_eq!(1, shake.shift(start + ms(120)));
+        assert_eq!(-1, shake.shift(start + ms(479)));
+        assert_eq!(0, shake.shift(start + ms(480)));
+
+        let later = Animation::new(Motion::Shake, 0, 5, start + ms(100));
+        assert_eq!(0, later.shift(start));
+        let reveal = Animation::new(Motion::Reveal, 0, 5, start);
+        assert_eq!(0, reveal.shift(start));
+    }
+
+    #[test]
+    fn test_lifted() {
+        let start = Instant::now();
+        let bounce = Animation::new(Motion::Bounce, 0, 5, start);
+        assert!(bounce.lifted(0, start));
+        assert!(!bounce.lifted(1, start));
+        // the next tile jumps half way through the previous one
+        assert!(bounce.lifted(0, start + ms(100)));
+        assert!(bounce.lifted(1, start + ms(100)));
+        assert!(!bounce.lifted(0, start + ms(200)));
+        assert!(bounce.lifted(4, start + ms(599)));
+        assert!(!bounce.lifted(4, start + ms(600)));
+
+        let reveal = Animation::new(Motion::Reveal, 0, 5, start);
+        assert!(!reveal.lifted(0, start));
+    }
+}
@@ -0,0 +1,149 @@
+use cursive::theme::*;
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Button, Dialog, EditView, LinearLayout, TextView};
+use cursive::Cursive;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use wordle_star::assistant::Assistant;
+use wordle_star::dictionary::Dictionary;
+use wordle_star::keyboard_layout::KeyboardLayout;
+use wordle_star::solver::Solver;
+
+use crate::board_view::BoardView;
+use crate::palette::Palette;
+
+/// Number of remaining candidates that are listed in full.
+const MAX_LISTED_CANDIDATES: usize = 10;
+
+/// Set up the assistant mode, used to solve a puzzle played elsewhere.
+/// The user enters each guess with the colours it received, e.g. "crane bgybb",
+/// and the board shows the guesses along with a suggested next guess.
+pub fn setup(
+    siv: &mut Cursive,
+    dict: &Dictionary,
+    solver: Solver,
+    layout: KeyboardLayout,
+    palette: Palette,
+) {
+    let word_length = dict.word_length;
+    let assistant = Rc::new(RefCell::new(Assistant::with_solver(solver, word_length)));
+
+    let gameboard = BoardView::new(assistant.clone(), layout)
+        .with_palette(palette)
+        .with_input("input");
+
+    let submit_assistant = assistant.clone();
+    let undo_assistant = assistant.clone();
+    let main_panel = LinearLayout::vertical()
+        .child(gameboard)
+        .child(TextView::new(status_message(&assistant.borrow())).with_name("status"))
+        .child(
+            LinearLayout::horizontal()
+                .child(
+                    EditView::new()
+                        // word, a space and the colours of each letter
+                        .max_content_width(word_length * 2 + 1)
+                        .on_submit(move |csiv, input| {
+                            csiv.call_on_name("input", |view: &mut EditView| {
+                                // clear the edit box first, allow user to enter next guess
+                                view.set_content("");
+                            });
+
+                            process_input(csiv, input, submit_assistant.clone());
+                        })
+                        .with_name("input")
+                        .fixed_width(word_length * 2 + 2),
+                )
+                .child(Button::new("Undo", move |csiv| {
+                    undo(csiv, undo_assistant.clone())
+                })),
+        );
+
+    siv.add_layer(main_panel);
+}
+
+/// Record a line of input, e.g. "crane bgybb", and refresh the suggestion.
+fn process_input(s: &mut Cursive, input: &str, shared_assistant: Rc<RefCell<Assistant>>) {
+    let result = shared_assistant
+        .borrow_mut()
+        .record_input(input)
+        .map(|_| ());
+    match result {
+        Err(err) => invalid_input_popup(s, &err.to_string(), shared_assistant),
+        Ok(()) => {
+            update_status(s, &shared_assistant.borrow());
+            if shared_assistant.borrow().is_solved() {
+                solved(s);
+            }
+        }
+    }
+}
+
+/// Undo the last guess, e.g. when its colours were entered incorrectly.
+fn undo(s: &mut Cursive, shared_assistant: Rc<RefCell<Assistant>>) {
+    shared_assistant.borrow_mut().undo();
+    update_status(s, &shared_assistant.borrow());
+}
+
+/// Show the number of candidates left and the suggested next guess.
+fn update_status(s: &mut Cursive, assistant: &Assistant) {
+    let message = status_message(assistant);
+    s.call_on_name("status", |view: &mut TextView| view.set_content(message));
+}
+
+fn status_message(assistant: &Assistant) -> StyledString {
+    let candidates = assistant.candidates();
+    let mut message = StyledString::plain(format!("{} candidates left.", candidates.len()));
+    if candidates.len() <= MAX_LISTED_CANDIDATES {
+        message.append_plain(format!(" ({})", candidates.join(", ")));
+    }
+    if let Some(suggestion) = assistant.suggest() {
+        message.append_plain("\nTry: ");
+        message.append(StyledString::styled(
+            suggestion,
+            Style::from(Color::Light(BaseColor::Green)).combine(Effect::Bold),
+        ));
+    }
+    message.append_plain("\nEnter a guess and its colours (b/y/g), e.g. crane bgybb");
+    message
+}
+
+/// Display popup explaining why the input was rejected, offering to undo the
+/// previous guess in case that one had a typo.
+fn invalid_input_popup(s: &mut Cursive, reason: &str, shared_assistant: Rc<RefCell<Assistant>>) {
+    let mut message = StyledString::plain("\n\n");
+    message.append(StyledString::styled(
+        reason,
+        Style::from(Color::Light(BaseColor::Red)).combine(Effect::Bold),
+    ));
+    message.append_plain("\n\n");
+
+    let mut dialog = Dialog::around(TextView::new(message))
+        .title("Oops!")
+        .button("Back", |s| {
+            s.pop_layer(); // remove this dialog when the button is pressed
+        });
+    if !shared_assistant.borrow().guess_results.is_empty() {
+        dialog.add_button("Undo last guess", move |s| {
+            s.pop_layer();
+            undo(s, shared_assistant.clone());
+        });
+    }
+    s.add_layer(dialog);
+}
+
+/// Show message after the puzzle has been solved.
+fn solved(s: &mut Cursive) {
+    let mut message = StyledString::plain("\n\n");
+    message.append(StyledString::styled(
+        "\tSolved! 🥳",
+        Style::from(Color::Light(BaseColor::Green)).combine(Effect::Bold),
+    ));
+    s.add_layer(
+        Dialog::around(TextView::new(message))
+            .title("Congratulations!")
+            .button("Ok", |s| s.quit()),
+    );
+}
@@ -0,0 +1,804 @@
+use cursive::{
+    direction::{Absolute, Direction},
+    event::{Event, EventResult, Key, MouseButton, MouseEvent},
+    theme::Effect,
+    view::{CannotFocus, View},
+    views::EditView,
+    Cursive, Printer, Vec2,
+};
+use std::cell::RefCell;
+use std::cmp;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use wordle_star::board::Board;
+use wordle_star::keyboard_layout::KeyboardLayout;
+
+use crate::animation::{Animation, Flip, Motion};
+use crate::palette::{Palette, TileStyle};
+
+/// Blank columns and lines around the board, and between its parts.
+const MARGIN: usize = 1;
+const GAP: usize = 4;
+
+/// Columns of the knowledge panel: the slot numbers and the alphabet.
+/// The counts below it are cut short if they do not fit.
+const KNOWLEDGE_WIDTH: usize = 32;
+
+/// Labels of the enter and backspace keys, drawn below the letters.
+const ENTER_LABEL: &str = "[enter]";
+const BACKSPACE_LABEL: &str = "[back]";
+
+/// A key of the on-screen keyboard.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum KeyPress {
+    Letter(char),
+    Enter,
+    Backspace,
+}
+
+/// Size of the tiles of the grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tiles {
+    /// tiles three columns wide, with borders between the rows
+    Wide,
+    /// one column per tile, and no borders between the rows
+    Compact,
+}
+
+impl Tiles {
+    /// Columns of a tile, including its left border.
+    fn width(self) -> usize {
+        match self {
+            Tiles::Wide => 4,
+            Tiles::Compact => 2,
+        }
+    }
+
+    /// Lines of a row, including its top border.
+    fn height(self) -> usize {
+        match self {
+            Tiles::Wide => 2,
+            Tiles::Compact => 1,
+        }
+    }
+}
+
+/// Where each part of the board is drawn, worked out from the size the
+/// board is given, the length of the words and the number of guesses.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Geometry {
+    tiles: Tiles,
+
+    /// top left corners of the grid and of the first row of keys
+    grid: Vec2,
+    keyboard: Vec2,
+
+    /// top left corner of the knowledge panel, if there is room for it
+    knowledge: Option<Vec2>,
+
+    /// size of the whole board
+    size: Vec2,
+}
+
+/// Callback of a submitted guess, which returns whether it was accepted.
+type Submit = Rc<dyn Fn(&mut Cursive, &str) -> bool>;
+
+/// Callback run once the animations of the board are over.
+type AfterAnimations = Box<dyn FnOnce(&mut Cursive)>;
+
+/// Time between two frames of the animations.
+const FRAME: Duration = Duration::from_millis(30);
+
+/// What is left of the animations of the board after a frame.
+enum Frame {
+    Running,
+    /// with the callback waiting for them, if any
+    Over(Option<AfterAnimations>),
+}
+
+/// A struct that wraps the wordle game, or any other board, into a view.
+/// Used to render a view of the Wordle board.
+/// The guess is either typed straight into the board, or into an input,
+/// and in both cases the on-screen keyboard can be clicked to edit it.
+pub struct BoardView {
+    game: Rc<RefCell<dyn Board>>,
+    layout: KeyboardLayout,
+    palette: Palette,
+
+    /// name of the `EditView` that clicked keys are sent to
+    input: Option<String>,
+
+    /// called with the typed guess when enter is pressed
+    submit: Option<Submit>,
+
+    /// letters of the guess typed so far, shared with the submit callback
+    /// so that they are only cleared once the guess is accepted
+    typed: Rc<RefCell<String>>,
+
+    /// where the parts of the board were laid out, None if they do not fit
+    geometry: Option<Geometry>,
+
+    /// whether rows are animated, off for reduced motion
+    motion: bool,
+    animations: Vec<Animation>,
+    after_animations: Option<AfterAnimations>,
+
+    /// set while a timer draws the frames of the animations
+    timer: Arc<AtomicBool>,
+}
+
+impl BoardView {
+    pub fn new(game: Rc<RefCell<dyn Board>>, layout: KeyboardLayout) -> BoardView {
+        BoardView {
+            game,
+            layout,
+            palette: Palette::default(),
+            input: None,
+            submit: None,
+            typed: Rc::new(RefCell::new(String::new())),
+            geometry: None,
+            motion: false,
+            animations: vec![],
+            after_animations: None,
+            timer: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Draw the tiles and keys in the colours of the palette.
+    pub fn with_palette(mut self, palette: Palette) -> BoardView {
+        self.palette = palette;
+        self
+    }
+
+    /// Flip the tiles of each guess over to reveal them, shake guesses that
+    /// are not accepted and bounce the winning guess. Animations started on
+    /// the board are drawn by `animate`.
+    pub fn with_animations(mut self, motion: bool) -> BoardView {
+        self.motion = motion;
+        self
+    }
+
+    /// Send keys clicked on the on-screen keyboard to the named `EditView`,
+    /// as if they were typed there.
+    pub fn with_input(mut self, name: &str) -> BoardView {
+        self.input = Some(name.to_string());
+        self
+    }
+
+    /// Type the guess into the next row of the board, and submit it on
+    /// enter. The guess is cleared if the callback accepts it.
+    pub fn on_submit<F>(mut self, submit: F) -> BoardView
+    where
+        F: Fn(&mut Cursive, &str) -> bool + 'static,
+    {
+        self.submit = Some(Rc::new(submit));
+        self
+    }
+
+    /// Forget the letters typed so far and stop the animations, e.g. when
+    /// a new game starts.
+    pub fn reset(&mut self) {
+        self.typed.borrow_mut().clear();
+        self.animations.clear();
+        self.after_animations = None;
+    }
+
+    /// Reveal the tiles of the last guess one after another, then bounce
+    /// them if it won. Returns false if animations are off.
+    pub fn reveal_guess(&mut self, won: bool) -> bool {
+        let row = match self.game.borrow().guess_results().len().checked_sub(1) {
+            Some(row) if self.motion => row,
+            _ => return false,
+        };
+        let tiles = self.game.borrow().word_length();
+        let reveal = Animation::new(Motion::Reveal, row, tiles, Instant::now());
+        self.animations.push(reveal);
+        if won {
+            let bounce = Animation::new(Motion::Bounce, row, tiles, reveal.end());
+            self.animations.push(bounce);
+        }
+        true
+    }
+
+    /// Shake the row of the guess being typed. Returns false if animations
+    /// are off.
+    pub fn shake(&mut self) -> bool {
+        if !self.motion {
+            return false;
+        }
+        let game = self.game.borrow();
+        let row = game.guess_results().len();
+        let shake = Animation::new(Motion::Shake, row, game.word_length(), Instant::now());
+        drop(game);
+        self.animations
+            .retain(|animation| animation.motion != Motion::Shake);
+        self.animations.push(shake);
+        true
+    }
+
+    /// Call back once the animations are over, e.g. to show the end of the
+    /// game after its last guess is revealed.
+    pub fn after_animations<F>(&mut self, callback: F)
+    where
+        F: FnOnce(&mut Cursive) + 'static,
+    {
+        self.after_animations = Some(Box::new(callback));
+    }
+
+    /// Drop the animations that are over, and take the callback waiting
+    /// for them once none are left.
+    fn next_frame(&mut self) -> Frame {
+        let now = Instant::now();
+        self.animations.retain(|animation| !animation.is_over(now));
+        if self.animations.is_empty() {
+            Frame::Over(self.after_animations.take())
+        } else {
+            Frame::Running
+        }
+    }
+
+    /// Number of rows of the grid: every guess allowed, or the guesses so
+    /// far and an empty row if there is no limit.
+    fn rows(&self) -> usize {
+        let game = self.game.borrow();
+        let guesses = game.guess_results().len();
+        match game.max_guesses() {
+            Some(max) => cmp::max(max, guesses),
+            None => guesses + 1,
+        }
+    }
+
+    /// Size of the grid of tiles.
+    fn grid_size(&self, tiles: Tiles) -> Vec2 {
+        let word_length = self.game.borrow().word_length();
+        let rows = self.rows();
+        Vec2::new(
+            word_length * tiles.width() + 1,
+            rows * tiles.height() + 3 - tiles.height(),
+        )
+    }
+
+    /// Size of the on-screen keyboard: each row of keys is followed by a
+    /// line for the markers of the palette, and the last one by the enter
+    /// and backspace keys.
+    fn keyboard_size(&self) -> Vec2 {
+        let special_keys = ENTER_LABEL.len() + 2 + BACKSPACE_LABEL.len();
+        Vec2::new(
+            cmp::max(self.layout.width(), special_keys),
+            self.layout.rows.len() * 2 + 1,
+        )
+    }
+
+    fn knowledge_size(&self) -> Vec2 {
+        Vec2::new(KNOWLEDGE_WIDTH, self.game.borrow().word_length() + 4)
+    }
+
+    /// Lay out the board in the available size. The keyboard goes right of
+    /// the grid, or under it on narrow terminals, and the knowledge panel
+    /// is only shown if there is room for it. Returns the layout that
+    /// fits, or the smallest layout as an error if none does.
+    fn geometry(&self, available: Vec2) -> Result<Geometry, Geometry> {
+        let margin = Vec2::new(MARGIN, MARGIN);
+        let keyboard = self.keyboard_size();
+        let knowledge = self.knowledge_size();
+
+        let mut candidates = vec![];
+        for tiles in [Tiles::Wide, Tiles::Compact] {
+            let grid = self.grid_size(tiles);
+            // keys are aligned with the letters of the first row
+            let beside = Vec2::new(MARGIN + grid.x + GAP, MARGIN + 1);
+            if tiles == Tiles::Wide {
+                let knowledge_at = Vec2::new(beside.x + keyboard.x + GAP, beside.y);
+                candidates.push(Geometry {
+                    tiles,
+                    grid: margin,
+                    keyboard: beside,
+                    knowledge: Some(knowledge_at),
+                    size: Vec2::new(
+                        knowledge_at.x + knowledge.x + MARGIN,
+                        cmp::max(grid.y, cmp::max(keyboard.y, knowledge.y) + 1) + 2 * MARGIN,
+                    ),
+                });
+            }
+            candidates.push(Geometry {
+                tiles,
+                grid: margin,
+                keyboard: beside,
+                knowledge: None,
+                size: Vec2::new(
+                    beside.x + keyboard.x + MARGIN,
+                    cmp::max(grid.y, keyboard.y + 1) + 2 * MARGIN,
+                ),
+            });
+            // keyboard under the grid
+            let under = Vec2::new(MARGIN, MARGIN + grid.y + 1);
+            candidates.push(Geometry {
+                tiles,
+                grid: margin,
+                keyboard: under,
+                knowledge: None,
+                size: Vec2::new(
+                    cmp::max(grid.x, keyboard.x) + 2 * MARGIN,
+                    under.y + keyboard.y + MARGIN,
+                ),
+            });
+        }
+
+        let smallest = *candidates
+            .iter()
+            .min_by_key(|geometry| (geometry.size.y, geometry.size.x))
+            .unwrap();
+        candidates
+            .into_iter()
+            .find(|geometry| geometry.size.fits_in(available))
+            .ok_or(smallest)
+    }
+
+    /// Row below the letters, with the enter and backspace keys.
+    fn special_keys_y(&self, geometry: &Geometry) -> usize {
+        geometry.keyboard.y + self.layout.rows.len() * 2
+    }
+
+    /// Column of the backspace key.
+    fn backspace_x(&self, geometry: &Geometry) -> usize {
+        geometry.keyboard.x + ENTER_LABEL.len() + 2
+    }
+
+    /// The key of the on-screen keyboard at the position, if any.
+    /// Each letter can be clicked on, or on the space to its right.
+    fn key_at(&self, position: Vec2) -> Option<KeyPress> {
+        let geometry = self.geometry.as_ref()?;
+        let keyboard_x = geometry.keyboard.x;
+        if position.y == self.special_keys_y(geometry) {
+            let enter = keyboard_x..keyboard_x + ENTER_LABEL.len();
+            let backspace_x = self.backspace_x(geometry);
+            let backspace = backspace_x..backspace_x + BACKSPACE_LABEL.len();
+            if enter.contains(&position.x) {
+                return Some(KeyPress::Enter);
+            }
+            if backspace.contains(&position.x) {
+                return Some(KeyPress::Backspace);
+            }
+            return None;
+        }
+
+        // keys are on every other line, markers of the palette in between
+        let line = position.y.checked_sub(geometry.keyboard.y)?;
+        if !line.is_multiple_of(2) {
+            return None;
+        }
+        let row = self.layout.rows.get(line / 2)?;
+        let x = position.x.checked_sub(keyboard_x + row.offset)?;
+        row.keys.get(x / 2).map(|&ch| KeyPress::Letter(ch))
+    }
+
+    /// Type the key into the board, or send it to the input and give it
+    /// the focus back.
+    fn press(&self, key: KeyPress) -> EventResult {
+        if let Some(submit) = &self.submit {
+            return self.type_key(key, submit.clone());
+        }
+
+        let input = match &self.input {
+            Some(input) => input.clone(),
+            None => return EventResult::Ignored,
+        };
+        let event = match key {
+            KeyPress::Letter(ch) => Event::Char(ch),
+            KeyPress::Enter => Event::Key(Key::Enter),
+            KeyPress::Backspace => Event::Key(Key::Backspace),
+        };
+        EventResult::with_cb(move |s: &mut Cursive| {
+            let result = s.call_on_name(&input, |view: &mut EditView| view.on_event(event.clone()));
+            // a missing input only means there is nothing to type into
+            let _ = s.focus_name(&input);
+            if let Some(result) = result {
+                result.process(s);
+            }
+        })
+    }
+
+    /// Edit the guess typed into the board, or submit it.
+    fn type_key(&self, key: KeyPress, submit: Submit) -> EventResult {
+        let word_length = self.game.borrow().word_length();
+        match key {
+            KeyPress::Letter(ch) if ch.is_alphabetic() => {
+                let mut typed = self.typed.borrow_mut();
+                if typed.chars().count() < word_length {
+                    typed.extend(ch.to_lowercase());
+                }
+                EventResult::Consumed(None)
+            }
+            KeyPress::Letter(_) => EventResult::Ignored,
+            KeyPress::Backspace => {
+                self.typed.borrow_mut().pop();
+                EventResult::Consumed(None)
+            }
+            KeyPress::Enter => {
+                let typed = self.typed.clone();
+                EventResult::with_cb(move |s: &mut Cursive| {
+                    let guess = typed.borrow().clone();
+                    if submit(s, &guess) {
+                        typed.borrow_mut().clear();
+                    }
+                })
+            }
+        }
+    }
+
+    /// Draw the wordle board, or ask for a bigger terminal if it does
+    /// not fit.
+    pub fn draw_board(&self, printer: &Printer) {
+        match &self.geometry {
+            Some(geometry) => {
+                self.draw_grid(printer, geometry);
+                self.draw_keyboard_view(printer, geometry);
+                if let Some(position) = geometry.knowledge {
+                    self.draw_knowledge(printer, position);
+                }
+            }
+            None => self.draw_too_small(printer),
+        }
+    }
+
+    /// Tell the player how big the terminal needs to be.
+    fn draw_too_small(&self, printer: &Printer) {
+        let needed = match self.geometry(Vec2::zero()) {
+            Ok(geometry) | Err(geometry) => geometry.size,
+        };
+        // two more lines, for the messages and the help under the board
+        printer.print((0, 0), "Terminal too small");
+        printer.print((0, 1), &format!("need {}x{}", needed.x, needed.y + 2));
+    }
+
+    /// Render the grid of tiles: one row per guess allowed, with the
+    /// guesses so far, followed by the guess being typed.
+    fn draw_grid(&self, printer: &Printer, geometry: &Geometry) {
+        let rows = self.rows();
+        let game = self.game.borrow();
+        let word_length = game.word_length();
+        let tiles = geometry.tiles;
+        let printer = printer.offset(geometry.grid);
+
+        // borders, shared between neighbouring tiles
+        let horizontal = "─".repeat(tiles.width() - 1);
+        let border_lines = match tiles {
+            Tiles::Wide => (0..=rows).collect::<Vec<_>>(),
+            Tiles::Compact => vec![0, rows],
+        };
+        for row in border_lines {
+            let (left, middle, right) = match row {
+                0 => ('┌', '┬', '┐'),
+                _ if row == rows => ('└', '┴', '┘'),
+                _ => ('├', '┼', '┤'),
+            };
+            let mut line = String::from(left);
+            for col in 0..word_length {
+                line.push_str(&horizontal);
+                line.push(if col + 1 == word_length {
+                    right
+                } else {
+                    middle
+                });
+            }
+            let y = match tiles {
+                Tiles::Wide => row * 2,
+                Tiles::Compact => row + usize::from(row > 0),
+            };
+            printer.print((0, y), &line);
+        }
+        for row in 0..rows {
+            for col in 0..=word_length {
+                printer.print((col * tiles.width(), letter_line(tiles, row)), "│");
+            }
+        }
+
+        let now = Instant::now();
+        let animation = |row: usize| {
+            self.animations
+                .iter()
+                .find(|animation| animation.row == row && animation.is_running(now))
+        };
+        for (row, guess_result) in game.guess_results().iter().enumerate() {
+            let animation = animation(row);
+            for (col, &(ch, state)) in guess_result.char_guesses.iter().enumerate() {
+                let mut position = tile_position(tiles, row, col);
+                // styled by whether each character is correct or not,
+                // once the tile has turned over
+                let tile = match animation.map(|animation| animation.flip(col, now)) {
+                    Some(Flip::Front) => None,
+                    Some(Flip::Edge) => {
+                        draw_tile_edge(&printer, tiles, position);
+                        continue;
+                    }
+                    _ => Some(self.palette.tile(Some(state))),
+                };
+                let lifted = animation.is_some_and(|animation| animation.lifted(col, now));
+                if lifted && tiles == Tiles::Wide {
+                    // over the border above, which compact rows do not have
+                    printer.print(position, &" ".repeat(tiles.width() - 1));
+                    position.y -= 1;
+                }
+                draw_grid_tile(&printer, tiles, position, ch, tile);
+            }
+        }
+
+        let row = game.guess_results().len();
+        if self.submit.is_some() && row < rows {
+            let shift = animation(row).map_or(0, |animation| animation.shift(now));
+            for (col, ch) in self.typed.borrow().chars().enumerate() {
+                let position = tile_position(tiles, row, col);
+                let position = Vec2::new(position.x.saturating_add_signed(shift), position.y);
+                draw_grid_tile(&printer, tiles, position, ch, None);
+            }
+        }
+    }
+
+    /// Render the keyboard in the configured layout, with hints
+    /// based on guesses so far.
+    fn draw_keyboard_view(&self, printer: &Printer, geometry: &Geometry) {
+        let game = self.game.borrow();
+        let keyboard = geometry.keyboard;
+        for (line_num, row) in self.layout.rows.iter().enumerate() {
+            for (pos, &ch) in row.keys.iter().enumerate() {
+                // style based on the keyboard view
+                let tile = self.palette.tile(game.keyboard_view().get(ch));
+                draw_tile(
+                    printer,
+                    (keyboard.x + row.offset + pos * 2, keyboard.y + line_num * 2),
+                    ch,
+                    tile,
+                );
+            }
+        }
+
+        if self.input.is_some() || self.submit.is_some() {
+            let y = self.special_keys_y(geometry);
+            printer.print((keyboard.x, y), ENTER_LABEL);
+            printer.print((self.backspace_x(geometry), y), BACKSPACE_LABEL);
+        }
+    }
+
+    /// Render the letters that may still be in each slot of the target,
+    /// one row per slot, followed by what is known about letter counts.
+    fn draw_knowledge(&self, printer: &Printer, position: Vec2) {
+        let game = self.game.borrow();
+        let knowledge = game.knowledge();
+        let (x, y) = (position.x, position.y + 2);
+
+        printer.print((x, y - 2), "slot  possible letters");
+        for pos in 0..knowledge.word_length() {
+            printer.print((x, y + pos), &format!("{:>4}", pos + 1));
+            for (i, ch) in ('a'..='z').enumerate() {
+                let cell = (x + 6 + i, y + pos);
+                if knowledge.fixed(pos) == Some(ch) {
+                    printer.with_style(self.palette.correct.style, |p| {
+                        p.print(cell, &ch.to_string())
+                    });
+                } else if knowledge.allows(pos, ch) {
+                    printer.print(cell, &ch.to_string());
+                } else {
+                    printer.print(cell, "·");
+                }
+            }
+        }
+
+        let counts: Vec<String> = knowledge
+            .letter_counts()
+            .into_iter()
+            .map(|(ch, min, max)| match max {
+                Some(max) if max == min => format!("{ch}: exactly {min}"),
+                Some(max) => format!("{ch}: {min} to {max}"),
+                None => format!("{ch}: at least {min}"),
+            })
+            .collect();
+        if !counts.is_empty() {
+            printer.print(
+                (x, y + knowledge.word_length() + 1),
+                &format!("counts  {}", counts.join(", ")),
+            );
+        }
+    }
+}
+
+/// Draw a letter as a key, with its marker on the line below, which is
+/// left free between the rows of keys.
+fn draw_tile(printer: &Printer, (x, y): (usize, usize), ch: char, tile: TileStyle) {
+    printer.with_style(tile.style, |p| p.print((x, y), &ch.to_string()));
+    if let Some(marker) = tile.marker {
+        printer.print((x, y + 1), &marker.to_string());
+    }
+}
+
+/// Line of the letters of a row of the grid.
+fn letter_line(tiles: Tiles, row: usize) -> usize {
+    row * tiles.height() + 1
+}
+
+/// Position of the letter of a tile of the grid, left of the padding.
+fn tile_position(tiles: Tiles, row: usize, col: usize) -> Vec2 {
+    Vec2::new(col * tiles.width() + 1, letter_line(tiles, row))
+}
+
+/// Draw a tile seen from the side, half way through turning over.
+fn draw_tile_edge(printer: &Printer, tiles: Tiles, position: Vec2) {
+    printer.print(position, &"─".repeat(tiles.width() - 1));
+}
+
+/// Draw a letter inside a tile of the grid, filling it with the style of
+/// the tile, with the marker right of the letter if there is room. Letters
+/// that have not been submitted have no tile style, and are only bold.
+fn draw_grid_tile(
+    printer: &Printer,
+    tiles: Tiles,
+    position: Vec2,
+    ch: char,
+    tile: Option<TileStyle>,
+) {
+    let text = match (tiles, tile.and_then(|tile| tile.marker)) {
+        (Tiles::Compact, _) => ch.to_string(),
+        (Tiles::Wide, Some(marker)) => format!(" {ch}{marker}"),
+        (Tiles::Wide, None) => format!(" {ch} "),
+    };
+    match tile {
+        Some(tile) => printer.with_style(tile.style, |p| p.print(position, &text)),
+        None => printer.with_effect(Effect::Bold, |p| p.print(position, &text)),
+    }
+}
+
+/// Cursive View to render the wordle board.
+impl View for BoardView {
+    fn draw(&self, printer: &Printer) {
+        self.draw_board(printer);
+    }
+
+    /// The biggest layout that fits, or all the room there is to explain
+    /// that nothing fits.
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        match self.geometry(constraint) {
+            Ok(geometry) => geometry.size,
+            Err(_) => constraint,
+        }
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.geometry = self.geometry(size).ok();
+    }
+
+    /// Typing into the board needs the focus. With an input, only take the
+    /// focus when clicked, so that the input keeps it otherwise.
+    fn take_focus(&mut self, source: Direction) -> Result<EventResult, CannotFocus> {
+        match (&self.submit, &self.input, source) {
+            (Some(_), _, _) => Ok(EventResult::Consumed(None)),
+            (None, Some(_), Direction::Abs(Absolute::None)) => Ok(EventResult::Consumed(None)),
+            _ => Err(CannotFocus),
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } => match position
+                .checked_sub(offset)
+                .and_then(|pos| self.key_at(pos))
+            {
+                Some(key) => self.press(key),
+                None => EventResult::Ignored,
+            },
+            // keys typed while the board has the focus
+            Event::Char(ch) => self.press(KeyPress::Letter(ch)),
+            Event::Key(Key::Enter) => self.press(KeyPress::Enter),
+            Event::Key(Key::Backspace) => self.press(KeyPress::Backspace),
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+/// Draw the animations of the named board frame by frame until they are
+/// over, then run the callback waiting for them. The frames come from a
+/// timer rather than refresh events, which only reach the top layer, so
+/// that the board keeps moving under a dialog.
+pub fn animate(s: &mut Cursive, name: &'static str) {
+    let timer = match s.call_on_name(name, |board: &mut BoardView| board.timer.clone()) {
+        Some(timer) => timer,
+        None => return,
+    };
+    if timer.swap(true, Ordering::Relaxed) {
+        // already drawing the animations started before
+        return;
+    }
+    let sink = s.cb_sink().clone();
+    thread::spawn(move || loop {
+        thread::sleep(FRAME);
+        if !timer.load(Ordering::Relaxed) {
+            break;
+        }
+        let timer = timer.clone();
+        // stops once the game is quit
+        if sink
+            .send(Box::new(move |s| frame(s, name, &timer)))
+            .is_err()
+        {
+            break;
+        }
+    });
+}
+
+fn frame(s: &mut Cursive, name: &str, timer: &AtomicBool) {
+    match s.call_on_name(name, BoardView::next_frame) {
+        Some(Frame::Running) => (),
+        Some(Frame::Over(callback)) => {
+            timer.store(false, Ordering::Relaxed);
+            if let Some(callback) = callback {
+                callback(s);
+            }
+        }
+        // the board is gone
+        None => timer.store(false, Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordle_star::dictionary::Dictionary;
+    use wordle_star::game::Game;
+
+    fn board() -> BoardView {
+        let mut dict = Dictionary::new(5);
+        dict.add_word_str("crane");
+        let game = Game::new(dict, "crane", 6);
+        let layout = KeyboardLayout::preset("qwerty").unwrap();
+        BoardView::new(Rc::new(RefCell::new(game)), layout)
+    }
+
+    fn layout(width: usize, height: usize) -> Result<Geometry, Geometry> {
+        board().geometry(Vec2::new(width, height))
+    }
+
+    #[test]
+    fn test_wide_with_knowledge() {
+        let geometry = layout(200, 60).unwrap();
+        assert_eq!(Tiles::Wide, geometry.tiles);
+        assert!(geometry.knowledge.is_some());
+        // keys start next to the first row of tiles
+        assert_eq!(geometry.grid.y + 1, geometry.keyboard.y);
+    }
+
+    #[test]
+    fn test_keyboard_beside() {
+        let geometry = layout(80, 24).unwrap();
+        assert_eq!(Tiles::Wide, geometry.tiles);
+        assert_eq!(None, geometry.knowledge);
+        assert_eq!(Vec2::new(26, 2), geometry.keyboard);
+        assert!(geometry.size.fits_in(Vec2::new(80, 24)));
+    }
+
+    #[test]
+    fn test_narrow_pane_stacks() {
+        let geometry = layout(30, 40).unwrap();
+        assert_eq!(Tiles::Wide, geometry.tiles);
+        assert_eq!(MARGIN, geometry.keyboard.x);
+        assert!(geometry.keyboard.y > geometry.grid.y + board().grid_size(Tiles::Wide).y);
+    }
+
+    #[test]
+    fn test_short_terminal_compact() {
+        let geometry = layout(40, 12).unwrap();
+        assert_eq!(Tiles::Compact, geometry.tiles);
+        assert_eq!(geometry.grid.y + 1, geometry.keyboard.y);
+    }
+
+    #[test]
+    fn test_too_small() {
+        let smallest = layout(10, 5).unwrap_err();
+        assert_eq!(Tiles::Compact, smallest.tiles);
+        assert_eq!(Vec2::new(37, 10), smallest.size);
+    }
+}
@@ -0,0 +1,155 @@
+use cursive::theme::{BaseColor, Color, Effect, Style};
+use cursive::traits::*;
+use cursive::views::NamedView;
+use cursive::{Cursive, Printer, Vec2};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+use wordle_star::game::Game;
+
+/// Time between two updates of the clock.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Time left under which the countdown is drawn in red.
+const HURRY: Duration = Duration::from_secs(10);
+
+/// Name of the clock of the game.
+const NAME: &str = "clock";
+
+/// How the games of a session are timed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Clock {
+    Off,
+    /// each game is lost once the time runs out
+    Countdown(Duration),
+    /// each game is timed, as is every series of that many games
+    Stopwatch {
+        series: Option<usize>,
+    },
+}
+
+impl Clock {
+    /// Mode of the games as kept in the stats, if they are timed.
+    pub fn mode(&self) -> Option<&'static str> {
+        match self {
+            Clock::Off => None,
+            Clock::Countdown(_) => Some("countdown"),
+            Clock::Stopwatch { .. } => Some("stopwatch"),
+        }
+    }
+}
+
+/// Callback run when the game runs out of time.
+type OutOfTime = Rc<dyn Fn(&mut Cursive)>;
+
+/// The time of the current game: the time left of a countdown, or the time
+/// taken so far, with the total of the series if there is one.
+pub struct ClockView {
+    game: Rc<RefCell<Game>>,
+    clock: Clock,
+
+    /// times of the games of the series that are over
+    series: Rc<RefCell<Vec<Duration>>>,
+    out_of_time: OutOfTime,
+}
+
+impl ClockView {
+    pub fn new<F>(
+        game: Rc<RefCell<Game>>,
+        clock: Clock,
+        series: Rc<RefCell<Vec<Duration>>>,
+        out_of_time: F,
+    ) -> NamedView<ClockView>
+    where
+        F: Fn(&mut Cursive) + 'static,
+    {
+        ClockView {
+            game,
+            clock,
+            series,
+            out_of_time: Rc::new(out_of_time),
+        }
+        .with_name(NAME)
+    }
+
+    fn text(&self) -> String {
+        let game = self.game.borrow();
+        match self.clock {
+            Clock::Off => String::new(),
+            Clock::Countdown(_) => {
+                // rounded up, so that the game is over at 0:00
+                let left = game.time_left().unwrap_or_default().as_secs_f64().ceil();
+                let left = Duration::from_secs(left as u64);
+                format!("  {} left", format_duration(left))
+            }
+            Clock::Stopwatch { series: None } => {
+                format!("  time {}", format_duration(game.duration()))
+            }
+            Clock::Stopwatch {
+                series: Some(length),
+            } => {
+                let series = self.series.borrow();
+                let total: Duration = series.iter().sum::<Duration>() + game.duration();
+                format!(
+                    "  time {}   word {} of {length}, total {}",
+                    format_duration(game.duration()),
+                    series.len() + 1,
+                    format_duration(total)
+                )
+            }
+        }
+    }
+
+    /// End the game if its time ran out, returning the callback to run.
+    fn tick(&mut self) -> Option<OutOfTime> {
+        let out_of_time = self.game.borrow_mut().check_time();
+        out_of_time.then(|| self.out_of_time.clone())
+    }
+}
+
+impl View for ClockView {
+    fn draw(&self, printer: &Printer) {
+        let hurry = self
+            .game
+            .borrow()
+            .time_left()
+            .is_some_and(|left| left < HURRY);
+        let style = if hurry {
+            Style::from(Color::Light(BaseColor::Red)).combine(Effect::Bold)
+        } else {
+            Style::from(Effect::Bold)
+        };
+        printer.with_style(style, |p| p.print((0, 0), &self.text()));
+    }
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(self.text().chars().count() + 2, 1)
+    }
+}
+
+/// Keep the clock running: redraw it every tick, and end the game when
+/// the time runs out.
+pub fn start(siv: &mut Cursive) {
+    let sink = siv.cb_sink().clone();
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        // stops once the game is quit
+        if sink.send(Box::new(tick)).is_err() {
+            break;
+        }
+    });
+}
+
+fn tick(s: &mut Cursive) {
+    if let Some(out_of_time) = s.call_on_name(NAME, ClockView::tick).flatten() {
+        out_of_time(s);
+    }
+}
+
+/// Minutes and seconds, e.g. 1:05.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
@@ -0,0 +1,174 @@
+use cursive::theme::Effect;
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, ScrollView, TextView};
+use cursive::Cursive;
+
+use wordle_star::guess::CharState;
+
+use crate::key_bindings::KeyBindings;
+use crate::palette::Palette;
+
+/// Name of the help dialog, so that it is only opened once.
+const NAME: &str = "help";
+
+/// What the help screen says about the current session.
+pub struct HelpInfo {
+    /// assisting with a puzzle played elsewhere, rather than playing
+    pub assist: bool,
+    pub keys: KeyBindings,
+    pub palette: Palette,
+    pub word_length: usize,
+    pub max_attempts: usize,
+    pub hard_mode: bool,
+
+    /// seconds each game may last, if it is limited
+    pub countdown: Option<u64>,
+
+    /// settings in use, by name, e.g. ("dictionary", "data/en-dict-5letters.txt")
+    pub settings: Vec<(&'static str, String)>,
+}
+
+/// Show the rules of the current mode, the keys and the settings in use.
+pub fn show(s: &mut Cursive, info: &HelpInfo) {
+    if s.find_name::<Dialog>(NAME).is_some() {
+        return;
+    }
+    let mut message = if info.assist {
+        assist_rules(info)
+    } else {
+        play_rules(info)
+    };
+
+    message.append(heading("\n\nKeys\n"));
+    for (key, action) in key_list(info) {
+        message.append_plain(format!("{key:<16}{action}\n"));
+    }
+
+    message.append(heading("\nSettings\n"));
+    for (name, value) in &info.settings {
+        message.append_plain(format!("{name:<16}{value}\n"));
+    }
+
+    s.add_layer(
+        Dialog::around(ScrollView::new(TextView::new(message)))
+            .title("Help")
+            .button("Back", |s| {
+                s.pop_layer();
+            })
+            .with_name(NAME),
+    );
+}
+
+fn play_rules(info: &HelpInfo) -> StyledString {
+    let mut rules = heading("How to play\n");
+    rules.append_plain(format!(
+        "Guess the hidden word in {} tries. Each guess must be a valid\n\
+         word of {} letters: type it, then press enter.\n\
+         The tiles then show how close the guess was:\n\n",
+        info.max_attempts, info.word_length
+    ));
+    rules.append(example(&info.palette, "crane", "gybbb"));
+    rules.append(explanation(&info.palette));
+    rules.append_plain("\nLetters may appear more than once in the word.\n");
+    if let Some(secs) = info.countdown {
+        rules.append_plain(format!(
+            "The clock is running: a word that is not found within {secs}\nseconds is lost.\n"
+        ));
+    }
+    if info.hard_mode {
+        rules.append_plain(
+            "Hard mode is on: letters that were revealed must be used\nin later guesses.\n",
+        );
+    }
+    rules
+}
+
+fn assist_rules(info: &HelpInfo) -> StyledString {
+    let mut rules = heading("How to use the assistant\n");
+    rules.append_plain(
+        "Enter each guess of a wordle played elsewhere, with the colours\n\
+         it got: g for green, y for yellow and b for grey, e.g. crane gybbb.\n\
+         The board then suggests the next guess:\n\n",
+    );
+    rules.append(example(&info.palette, "crane", "gybbb"));
+    rules.append(explanation(&info.palette));
+    rules
+}
+
+/// A row of tiles in the colours of the palette, with one of g, y or b per
+/// letter.
+fn example(palette: &Palette, word: &str, colours: &str) -> StyledString {
+    let mut row = StyledString::plain("  ");
+    for (ch, colour) in word.chars().zip(colours.chars()) {
+        let tile = palette.tile(Some(state(colour)));
+        let marker = tile.marker.unwrap_or(' ');
+        row.append_styled(format!(" {ch}{marker}"), tile.style);
+        row.append_plain(" ");
+    }
+    row.append_plain("\n\n");
+    row
+}
+
+/// What each colour of the example means.
+fn explanation(palette: &Palette) -> StyledString {
+    let mut text = StyledString::new();
+    for (ch, colour, meaning) in [
+        ('c', 'g', "is in the word, in the right spot"),
+        ('r', 'y', "is in the word, in another spot"),
+        ('a', 'b', "is not in the word, nor are n and e"),
+    ] {
+        let tile = palette.tile(Some(state(colour)));
+        let marker = tile.marker.unwrap_or(' ');
+        text.append_plain("  ");
+        text.append_styled(format!(" {ch}{marker}"), tile.style);
+        text.append_plain(format!("  {meaning}\n"));
+    }
+    text
+}
+
+fn state(colour: char) -> CharState {
+    match colour {
+        'g' => CharState::CorrectPosition,
+        'y' => CharState::IncorrectPosition,
+        _ => CharState::NotFound,
+    }
+}
+
+/// Keys of the current mode, and what they do.
+fn key_list(info: &HelpInfo) -> Vec<(String, &'static str)> {
+    let keys = &info.keys;
+    let mut list = vec![];
+    if info.assist {
+        list.push(("a-z, space".to_string(), "type a guess and its colours"));
+        list.push(("enter".to_string(), "record the guess"));
+        list.push(("tab".to_string(), "move to the undo button"));
+    } else {
+        list.push(("a-z".to_string(), "type a letter of the guess"));
+        list.push(("backspace".to_string(), "delete the last letter"));
+        list.push(("enter".to_string(), "submit the guess"));
+        list.push((keys.statistics.name.clone(), "statistics"));
+        list.push((keys.give_up.name.clone(), "give up"));
+        list.push((keys.messages.name.clone(), "messages"));
+        list.push((
+            keys.history.name.clone(),
+            "history of the games, to replay them",
+        ));
+    }
+    list.push((
+        "mouse".to_string(),
+        "click the keys of the on-screen keyboard",
+    ));
+    // ? is typed into the input of the assistant
+    let help = if info.assist {
+        keys.help.name.clone()
+    } else {
+        format!("{} or ?", keys.help.name)
+    };
+    list.push((help, "this help"));
+    list
+}
+
+fn heading(text: &str) -> StyledString {
+    StyledString::styled(text, Effect::Bold)
+}
@@ -0,0 +1,136 @@
+use cursive::event::Key;
+use cursive::traits::*;
+use cursive::views::{Dialog, LinearLayout, OnEventView, ScrollView, SelectView, TextView};
+use cursive::Cursive;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use wordle_star::dictionary::Dictionary;
+use wordle_star::keyboard_layout::KeyboardLayout;
+use wordle_star::replay::Replay;
+use wordle_star::stats::{GameRecord, Outcome, StatsStore};
+
+use crate::board_view::BoardView;
+use crate::clock;
+use crate::palette::Palette;
+
+/// Name of the line under a replayed board, with the step shown.
+const STEP_NAME: &str = "replay step";
+
+/// What recorded games are replayed with: the dictionary of the session
+/// and the same board as in play.
+pub struct Replayer {
+    pub dict: Dictionary,
+    pub max_guesses: usize,
+    pub layout: KeyboardLayout,
+    pub palette: Palette,
+}
+
+/// List the games of the stats store, newest first. Selecting one replays
+/// it.
+pub fn show(s: &mut Cursive, replayer: Rc<Replayer>) {
+    if s.find_name::<SelectView<GameRecord>>("history").is_some() {
+        return;
+    }
+    let records = match StatsStore::open_default().map(|store| store.load()) {
+        Some(Ok(records)) => records,
+        Some(Err(err)) => {
+            s.add_layer(Dialog::info(format!("Could not load the history: {err}")));
+            return;
+        }
+        None => vec![],
+    };
+    if records.is_empty() {
+        s.add_layer(Dialog::info("No games played yet."));
+        return;
+    }
+
+    let mut list = SelectView::new();
+    for record in records.into_iter().rev() {
+        list.add_item(describe(&record), record);
+    }
+    list.set_on_submit(move |s, record: &GameRecord| replay(s, &replayer, record.clone()));
+
+    s.add_layer(
+        Dialog::around(ScrollView::new(list.with_name("history")).max_height(15))
+            .title("History")
+            .button("Back", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+/// Date, mode, target and result of a game, on one line.
+fn describe(record: &GameRecord) -> String {
+    let result = match record.result {
+        Outcome::Win => format!("won in {}", record.guesses.len()),
+        Outcome::Lose => format!("lost after {}", record.guesses.len()),
+        Outcome::OutOfTime => "out of time".to_string(),
+    };
+    let time = clock::format_duration(Duration::from_secs(record.duration_secs));
+    format!(
+        "{}  {:<9} {}  {time:>5}  {result}",
+        record.date, record.mode, record.target
+    )
+}
+
+/// Replay a game on the board, one guess at a time with the arrow keys or
+/// the buttons.
+fn replay(s: &mut Cursive, replayer: &Replayer, record: GameRecord) {
+    let title = describe(&record);
+    let replay = match Replay::new(record, replayer.dict.clone(), replayer.max_guesses) {
+        Ok(replay) => Rc::new(RefCell::new(replay)),
+        Err(err) => {
+            s.add_layer(Dialog::info(format!("Cannot replay this game: {err}")));
+            return;
+        }
+    };
+
+    let board =
+        BoardView::new(replay.clone(), replayer.layout.clone()).with_palette(replayer.palette);
+    let step = TextView::new(step_message(&replay.borrow())).with_name(STEP_NAME);
+    let dialog = Dialog::around(LinearLayout::vertical().child(board).child(step))
+        .title(title)
+        .button("Previous", {
+            let replay = replay.clone();
+            move |s| go(s, &replay, Replay::back)
+        })
+        .button("Next", {
+            let replay = replay.clone();
+            move |s| go(s, &replay, Replay::forward)
+        })
+        .button("Back", |s| {
+            s.pop_layer();
+        });
+
+    let previous = replay.clone();
+    s.add_layer(
+        OnEventView::new(dialog)
+            .on_pre_event(Key::Left, move |s| go(s, &previous, Replay::back))
+            .on_pre_event(Key::Right, move |s| go(s, &replay, Replay::forward)),
+    );
+}
+
+/// Take a step of the replay, and say where it is.
+fn go(s: &mut Cursive, replay: &RefCell<Replay>, step: fn(&mut Replay) -> bool) {
+    step(&mut replay.borrow_mut());
+    let message = step_message(&replay.borrow());
+    s.call_on_name(STEP_NAME, |view: &mut TextView| view.set_content(message));
+}
+
+fn step_message(replay: &Replay) -> String {
+    let mut message = format!(
+        "  guess {} of {}   ←/→: step through",
+        replay.step(),
+        replay.steps()
+    );
+    if replay.step() == replay.steps() {
+        message.push_str(match replay.record().result {
+            Outcome::Win => "   won",
+            Outcome::Lose => "   lost",
+            Outcome::OutOfTime => "   out of time",
+        });
+    }
+    message
+}
@@ -0,0 +1,186 @@
+use std::io::{self, BufRead, Write};
+use wordle_star::game::GameState;
+use wordle_star::protocol::{Request, Response};
+
+use crate::Session;
+
+/// Play headless for bots: one JSON request per line on stdin, one JSON
+/// response per line on stdout, and nothing else on stdout. The first game
+/// is announced right away.
+pub fn run(session: &Session) -> io::Result<()> {
+    serve(session, io::stdin().lock(), &mut io::stdout())
+}
+
+fn serve(session: &Session, input: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    send(out, &Response::new_game(&session.game.borrow()))?;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match Request::parse(&line) {
+            Ok(request) => respond(session, request),
+            Err(error) => error,
+        };
+        send(out, &response)?;
+    }
+    Ok(())
+}
+
+fn respond(session: &Session, request: Request) -> Response {
+    match request {
+        Request::NewGame => {
+            check_time(session);
+            if session.game.borrow().state == GameState::Playing {
+                return Response::error("the game is not over yet");
+            }
+            session.next_round();
+            Response::new_game(&session.game.borrow())
+        }
+        Request::Guess { word } => {
+            let playing = session.game.borrow().state == GameState::Playing;
+            let response = Response::guess(&mut session.game.borrow_mut(), &word);
+            // the guess won or lost the game, or came too late
+            if playing && session.game.borrow().state != GameState::Playing {
+                record_game(session);
+            }
+            response
+        }
+        Request::State => {
+            check_time(session);
+            Response::state(&session.game.borrow())
+        }
+    }
+}
+
+/// End the game if its time ran out, so that it is never reported as being
+/// played after that.
+fn check_time(session: &Session) {
+    if session.game.borrow_mut().check_time() {
+        record_game(session);
+    }
+}
+
+fn record_game(session: &Session) {
+    if let Err(err) = session.record_game() {
+        eprintln!("could not save statistics: {err}");
+    }
+}
+
+fn send(out: &mut impl Write, response: &Response) -> io::Result<()> {
+    writeln!(out, "{}", response.to_line())?;
+    // bots wait for each response before sending the next request
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::session;
+    use std::fs;
+    use std::io::Cursor;
+    use std::time::Duration;
+    use wordle_star::stats::{Outcome, StatsStore};
+
+    fn serve_lines(lines: &[&str]) -> Vec<String> {
+        serve_session(&session(), lines)
+    }
+
+    fn serve_session(session: &Session, lines: &[&str]) -> Vec<String> {
+        let mut out = vec![];
+        serve(session, Cursor::new(lines.join("\n")), &mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_one_response_per_request() {
+        let responses = serve_lines(&[
+            r#"{"type": "guess", "word": "tab"}"#,
+            "",
+            "guess cat",
+            r#"{"type": "state"}"#,
+        ]);
+        assert_eq!(4, responses.len());
+        assert_eq!(
+            r#"{"type":"new-game","word_length":3,"max_attempts":3,"hard_mode":false}"#,
+            responses[0]
+        );
+        assert!(responses[1].starts_with(r#"{"type":"guess","guess":{"word":"tab","#));
+        assert!(responses[2].starts_with(r#"{"type":"error","message":"invalid request: "#));
+        assert!(responses[3].starts_with(r#"{"type":"state","guesses":[{"word":"tab","#));
+    }
+
+    #[test]
+    fn test_new_game() {
+        let responses = serve_lines(&[
+            r#"{"type": "new-game"}"#,
+            r#"{"type": "guess", "word": "cat"}"#,
+            r#"{"type": "guess", "word": "bat"}"#,
+            r#"{"type": "new-game"}"#,
+            r#"{"type": "state"}"#,
+        ]);
+        assert_eq!(
+            r#"{"type":"error","message":"the game is not over yet"}"#,
+            responses[1]
+        );
+        assert!(responses[2].ends_with(r#""state":"win","target":"cat"}"#));
+        assert_eq!(
+            r#"{"type":"error","message":"the game is over, start a new game"}"#,
+            responses[3]
+        );
+        assert_eq!(responses[0], responses[4]);
+        assert_eq!(
+            r#"{"type":"state","guesses":[],"remaining_attempts":3,"state":"playing"}"#,
+            responses[5]
+        );
+    }
+
+    /// A session whose games are out of time as soon as they start, and
+    /// are recorded in a store of their own.
+    fn out_of_time_session(name: &str) -> Session {
+        let path = std::env::temp_dir().join(format!(
+            "wordle-star-jsonl-{name}-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let session = Session {
+            stats: Some(StatsStore::new(path)),
+            ..session()
+        };
+        session
+            .game
+            .borrow_mut()
+            .set_time_limit(Some(Duration::ZERO));
+        session
+    }
+
+    fn recorded(session: &Session) -> Vec<Outcome> {
+        let store = session.stats.as_ref().unwrap();
+        let outcomes = store.load().unwrap().iter().map(|r| r.result).collect();
+        fs::remove_file(store.path()).unwrap();
+        outcomes
+    }
+
+    #[test]
+    fn test_state_out_of_time() {
+        let session = out_of_time_session("state");
+        let responses = serve_session(&session, &[r#"{"type": "state"}"#]);
+        assert_eq!(
+            r#"{"type":"state","guesses":[],"remaining_attempts":3,"state":"out-of-time","target":"cat"}"#,
+            responses[1]
+        );
+        assert_eq!(vec![Outcome::OutOfTime], recorded(&session));
+    }
+
+    #[test]
+    fn test_new_game_out_of_time() {
+        let session = out_of_time_session("new-game");
+        let responses = serve_session(&session, &[r#"{"type": "new-game"}"#]);
+        assert_eq!(responses[0], responses[1]);
+        assert_eq!(vec![Outcome::OutOfTime], recorded(&session));
+    }
+}
@@ -0,0 +1,174 @@
+use cursive::event::{Event, Key};
+use std::collections::BTreeMap;
+
+/// Keys that the board and the dialogs already use.
+const RESERVED: [Key; 3] = [Key::Esc, Key::Tab, Key::Del];
+
+/// Key that triggers an action, with the name it is shown as.
+#[derive(Clone)]
+pub struct Binding {
+    pub event: Event,
+    pub name: String,
+}
+
+/// Keys of the actions of a game, which may be changed in the config file.
+/// Letters are typed into the guess, so only other keys can be bound.
+#[derive(Clone)]
+pub struct KeyBindings {
+    pub statistics: Binding,
+    pub give_up: Binding,
+
+    /// history of the messages of the status line
+    pub messages: Binding,
+
+    /// games played so far, which can be replayed
+    pub history: Binding,
+    pub help: Binding,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            statistics: parse_key("F2").unwrap(),
+            give_up: parse_key("F3").unwrap(),
+            messages: parse_key("F4").unwrap(),
+            history: parse_key("F5").unwrap(),
+            help: parse_key("F1").unwrap(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Default bindings, changed by the keys of the config file, which are
+    /// indexed by name of the action. Each action needs a key of its own,
+    /// other than the keys the game already uses.
+    pub fn from_config(keys: &BTreeMap<String, String>) -> Result<KeyBindings, String> {
+        let mut bindings = KeyBindings::default();
+        for (action, key) in keys {
+            let binding = parse_key(key).ok_or_else(|| {
+                format!("unknown key {key} for {action}, expected e.g. F5, home or ctrl-g")
+            })?;
+            if RESERVED
+                .iter()
+                .any(|&reserved| binding.event == Event::Key(reserved))
+            {
+                return Err(format!(
+                    "key {key} for {action} is already used by the game"
+                ));
+            }
+            match action.as_str() {
+                "statistics" => bindings.statistics = binding,
+                "give-up" => bindings.give_up = binding,
+                "messages" => bindings.messages = binding,
+                "history" => bindings.history = binding,
+                "help" => bindings.help = binding,
+                _ => {
+                    return Err(format!(
+                        "unknown action {action}, expected statistics, give-up, messages, history or help"
+                    ))
+                }
+            }
+        }
+        let actions = bindings.actions();
+        for (index, (action, binding)) in actions.iter().enumerate() {
+            if let Some((other, _)) = actions[index + 1..]
+                .iter()
+                .find(|(_, other)| other.event == binding.event)
+            {
+                return Err(format!(
+                    "{action} and {other} are both bound to {}",
+                    binding.name
+                ));
+            }
+        }
+        Ok(bindings)
+    }
+
+    /// Every action, by the name used in the config file, with its key.
+    fn actions(&self) -> [(&'static str, &Binding); 5] {
+        [
+            ("statistics", &self.statistics),
+            ("give-up", &self.give_up),
+            ("messages", &self.messages),
+            ("history", &self.history),
+            ("help", &self.help),
+        ]
+    }
+}
+
+/// Parse a key name like "F5", "esc", "page-down" or "ctrl-g".
+fn parse_key(name: &str) -> Option<Binding> {
+    let lower = name.to_lowercase();
+    let event = if let Some(letter) = lower.strip_prefix("ctrl-") {
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) if ch.is_ascii_lowercase() => Event::CtrlChar(ch),
+            _ => return None,
+        }
+    } else if let Some(number) = lower.strip_prefix('f') {
+        match number.parse::<u8>().ok()? {
+            number @ 1..=12 => Event::Key(Key::from_f(number)),
+            _ => return None,
+        }
+    } else {
+        let key = match lower.as_str() {
+            "esc" => Key::Esc,
+            "tab" => Key::Tab,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "page-up" => Key::PageUp,
+            "page-down" => Key::PageDown,
+            "insert" => Key::Ins,
+            "delete" => Key::Del,
+            _ => return None,
+        };
+        Event::Key(key)
+    };
+    Some(Binding {
+        event,
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_config(keys: &[(&str, &str)]) -> Result<KeyBindings, String> {
+        let keys = keys
+            .iter()
+            .map(|(action, key)| (action.to_string(), key.to_string()))
+            .collect();
+        KeyBindings::from_config(&keys)
+    }
+
+    #[test]
+    fn test_from_config() {
+        let bindings = from_config(&[("history", "ctrl-h"), ("help", "F5")]).unwrap();
+        assert_eq!(Event::CtrlChar('h'), bindings.history.event);
+        assert_eq!(Event::Key(Key::F5), bindings.help.event);
+        assert_eq!("F5", bindings.help.name);
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        assert_eq!(
+            Some("statistics and give-up are both bound to F3".to_string()),
+            from_config(&[("statistics", "F3")]).err()
+        );
+        assert_eq!(
+            Some("messages and help are both bound to home".to_string()),
+            from_config(&[("help", "home"), ("messages", "home")]).err()
+        );
+    }
+
+    #[test]
+    fn test_reserved_keys() {
+        for key in ["esc", "tab", "delete"] {
+            assert_eq!(
+                Some(format!("key {key} for help is already used by the game")),
+                from_config(&[("help", key)]).err()
+            );
+        }
+    }
+}
@@ -0,0 +1,1074 @@
+mod accessible;
+mod animation;
+mod assist;
+mod board_view;
+mod clock;
+mod help_screen;
+mod history_screen;
+mod jsonl;
+mod key_bindings;
+mod palette;
+mod plain;
+mod share_screen;
+mod stats_screen;
+mod status_line;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use cursive::theme::*;
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, TextView};
+use cursive::{
+    theme::{self, BaseColor, Color, Effect},
+    views::LinearLayout,
+    Cursive,
+};
+
+use chrono::Local;
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
+use std::time::Duration;
+use wordle_star::config::{Config, Settings};
+use wordle_star::dictionary::Dictionary;
+use wordle_star::difficulty::{self, Difficulty, DifficultyRating};
+use wordle_star::game::Game;
+use wordle_star::game::GameState;
+use wordle_star::game::GuessError;
+use wordle_star::game::SubmitError;
+use wordle_star::keyboard_layout::{self, KeyboardLayout};
+use wordle_star::pattern;
+use wordle_star::share::{self, ShareStyle};
+use wordle_star::solver::Solver;
+use wordle_star::stats::{GameRecord, StatsStore};
+use wordle_star::targets::{self, TargetPicker};
+
+use board_view::BoardView;
+use clock::{Clock, ClockView};
+use help_screen::HelpInfo;
+use history_screen::Replayer;
+use key_bindings::KeyBindings;
+use palette::Palette;
+use share_screen::ShareSettings;
+use stats_screen::NewGame;
+use status_line::StatusLine;
+
+/// A wordle clone for the terminal.
+///
+/// Defaults for most options may be set in config.toml in the wordle-star
+/// config directory, with named profiles; options given here override them.
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    /// Config file with default options, defaults to config.toml in the
+    /// wordle-star config directory
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Profile of the config file to use on top of its defaults
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// What to do: play a game, or get help solving a puzzle played elsewhere
+    #[arg(long, value_enum, default_value_t = Mode::Play)]
+    mode: Mode,
+
+    /// Words that may be guessed, one per line
+    #[arg(long, default_value = "data/en-dict-5letters.txt")]
+    dict: PathBuf,
+
+    /// Words the targets are picked from, one per line, defaults to the
+    /// whole dictionary
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Number of letters of every word in the dictionary
+    #[arg(long, default_value_t = 5)]
+    word_length: usize,
+
+    /// Number of guesses allowed per game
+    #[arg(long, default_value_t = 6)]
+    max_attempts: usize,
+
+    /// Seed for picking targets, to play the same games again
+    #[arg(long, conflicts_with_all = ["daily", "target"])]
+    seed: Option<u64>,
+
+    /// Play today's puzzle, the same for everyone with the same answers
+    #[arg(long, conflicts_with_all = ["target", "difficulty"])]
+    daily: bool,
+
+    /// Every guess must use the letters revealed so far
+    #[arg(long)]
+    hard: bool,
+
+    /// Target word of the first game, for testing
+    #[arg(long, conflicts_with = "difficulty")]
+    target: Option<String>,
+
+    /// Only pick target words of this difficulty, if the dictionary is rated
+    #[arg(long, value_enum)]
+    difficulty: Option<Level>,
+
+    /// Cursive theme file with the colours of the interface
+    #[arg(long, default_value = "assets/style.toml")]
+    theme: PathBuf,
+
+    /// Colours of the tiles: classic, colour-blind, high-contrast, or
+    /// monochrome, which marks tiles with symbols; overrides the palette of
+    /// the theme file
+    #[arg(long)]
+    palette: Option<String>,
+
+    /// Layout of the on-screen keyboard: qwerty, azerty, qwertz, dvorak,
+    /// colemak, alphabetical, or a layout from the layouts file
+    #[arg(long, default_value = "qwerty")]
+    layout: String,
+
+    /// TOML file with custom keyboard layouts, defaults to layouts.toml in
+    /// the wordle-star config directory
+    #[arg(long)]
+    layout_file: Option<PathBuf>,
+
+    /// Lose a game that is not solved within this many seconds
+    #[arg(long)]
+    countdown: Option<u64>,
+
+    /// Time each game, and show the clock
+    #[arg(long)]
+    stopwatch: bool,
+
+    /// Number of games of a stopwatch series, which is also timed in total
+    #[arg(long)]
+    series: Option<usize>,
+
+    /// Do not animate the tiles, nor shake guesses that are not accepted
+    #[arg(long)]
+    reduced_motion: bool,
+
+    /// How the game is shown: on a board, read out in lines of text for
+    /// screen readers, or in plain lines on stdin and stdout for scripts
+    /// and dumb terminals
+    #[arg(long, value_enum, default_value_t = Interface::Tui)]
+    interface: Interface,
+
+    /// Play headless instead, speaking this protocol on stdin and stdout,
+    /// for bots
+    #[arg(long, value_enum, conflicts_with = "interface")]
+    protocol: Option<Protocol>,
+
+    /// Symbols of the shareable result grid
+    #[arg(long, value_enum, default_value_t = ShareFormat::Standard)]
+    share_style: ShareFormat,
+
+    /// Save the shareable result to this file when the game is over
+    #[arg(long)]
+    share_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Mode {
+    /// Guess a random target word
+    Play,
+    /// Enter guesses and colours from another wordle, get suggestions
+    Assist,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Interface {
+    /// Board and keyboard drawn in colour
+    Tui,
+    /// Plain lines of text that announce every guess, for screen readers
+    Accessible,
+    /// A row of tiles per guess on stdout, in colour unless NO_COLOR is set
+    Plain,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Protocol {
+    /// One JSON object per line: new-game, guess and state requests, and
+    /// new-game, guess, state and error responses
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Level {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl From<Level> for Difficulty {
+    fn from(level: Level) -> Difficulty {
+        match level {
+            Level::Easy => Difficulty::Easy,
+            Level::Medium => Difficulty::Medium,
+            Level::Hard => Difficulty::Hard,
+            Level::Expert => Difficulty::Expert,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ShareFormat {
+    /// 🟩 🟨 ⬛
+    Standard,
+    /// 🟧 🟦 ⬛
+    HighContrast,
+    /// G Y -
+    Ascii,
+}
+
+impl From<ShareFormat> for ShareStyle {
+    fn from(format: ShareFormat) -> ShareStyle {
+        match format {
+            ShareFormat::Standard => ShareStyle::Standard,
+            ShareFormat::HighContrast => ShareStyle::HighContrast,
+            ShareFormat::Ascii => ShareStyle::Ascii,
+        }
+    }
+}
+
+/// State shared by the rounds of a session of games.
+struct Session {
+    game: Rc<RefCell<Game>>,
+    targets: RefCell<TargetPicker>,
+
+    /// how the target of the current round was picked, as kept in the stats
+    mode: Cell<&'static str>,
+
+    /// number of the daily puzzle, if that is the current round
+    puzzle: Cell<Option<i64>>,
+
+    /// how the games are timed, and the times of the games of the current
+    /// series so far
+    clock: Clock,
+    series: Rc<RefCell<Vec<Duration>>>,
+
+    /// where finished games are recorded, None to not record them
+    stats: Option<StatsStore>,
+}
+
+impl Session {
+    /// Start the next round with a random target word.
+    fn next_round(&self) {
+        let target_word = self.targets.borrow_mut().pick();
+        self.game.borrow_mut().new_round(&target_word);
+        self.mode.set(self.clock.mode().unwrap_or("play"));
+        self.puzzle.set(None);
+    }
+
+    /// Record the game of the current round in the stats, once it is over.
+    fn record_game(&self) -> io::Result<()> {
+        match &self.stats {
+            Some(store) => {
+                store.record(&GameRecord::from_game(&self.game.borrow(), self.mode.get()))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn main() {
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let settings = load_settings(args.config.as_deref(), args.profile.as_deref());
+    let keys = KeyBindings::from_config(&settings.keys).unwrap_or_else(|err| fail(err));
+    if let Err(message) = apply_settings(&mut args, &matches, settings) {
+        fail(message);
+    }
+    if let Err(message) = validate_args(&args) {
+        fail(message);
+    }
+
+    let mut dict = load_dict(&args.dict, args.word_length);
+    load_difficulty(&mut dict, &args.dict);
+
+    if let Some(Protocol::Jsonl) = args.protocol {
+        return play_lines(&args, dict, jsonl::run);
+    }
+    match args.interface {
+        Interface::Tui => (),
+        Interface::Accessible => return play_lines(&args, dict, accessible::run),
+        Interface::Plain => return play_lines(&args, dict, plain::run),
+    }
+
+    let layout = load_layout(&args.layout, args.layout_file.as_deref());
+
+    let theme = load_theme(&args.theme);
+    let palette = load_palette(&args.theme, args.palette.as_deref());
+
+    let mut siv = cursive::default();
+    siv.set_theme(theme);
+    siv.set_user_data(ShareSettings {
+        style: args.share_style.into(),
+        hard_mode: args.hard,
+        file: args.share_file.clone(),
+        text: None,
+    });
+
+    let help = Rc::new(help_info(&args, &keys, palette));
+    siv.add_global_callback(keys.help.event.clone(), {
+        let help = help.clone();
+        move |s| help_screen::show(s, &help)
+    });
+    if args.mode == Mode::Play {
+        // typed into the input of the assistant
+        siv.add_global_callback('?', move |s| help_screen::show(s, &help));
+    }
+
+    match args.mode {
+        Mode::Play => {
+            let session = new_session(&args, dict);
+            let motion = !args.reduced_motion;
+            setup_game(&mut siv, session, layout, palette, motion, keys)
+        }
+        Mode::Assist => {
+            let solver = load_solver(&dict);
+            assist::setup(&mut siv, &dict, solver, layout, palette)
+        }
+    }
+
+    siv.run();
+
+    // the result of a finished game, to paste into chats
+    if let Some(text) = siv
+        .take_user_data::<ShareSettings>()
+        .and_then(|settings| settings.text)
+    {
+        println!("{text}");
+    }
+}
+
+/// Play a session with one of the front ends that read lines of stdin,
+/// without cursive.
+fn play_lines(args: &Args, dict: Dictionary, run: fn(&Session) -> io::Result<()>) {
+    let session = new_session(args, dict);
+    if let Err(err) = run(&session) {
+        fail(err);
+    }
+}
+
+/// Print an error about the arguments and exit.
+fn fail(message: impl Display) -> ! {
+    eprintln!("error: {message}");
+    process::exit(1);
+}
+
+/// Load the settings of the config file, and of a profile if one is given.
+/// A missing config file is only an error if it was asked for explicitly.
+/// Exits with an error message if the file or the profile cannot be loaded.
+fn load_settings(config_file: Option<&Path>, profile: Option<&str>) -> Settings {
+    let path = match (config_file, Config::default_path()) {
+        (Some(path), _) => Some(path.to_path_buf()),
+        (None, Some(path)) if path.exists() => Some(path),
+        _ => None,
+    };
+    let config = match &path {
+        None => Config::default(),
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| Config::parse(&contents).map_err(|err| err.to_string()))
+            .unwrap_or_else(|err| fail(format!("could not load config {}: {err}", path.display()))),
+    };
+    config.settings(profile).unwrap_or_else(|err| match path {
+        Some(path) => fail(format!("{err} in {}", path.display())),
+        None => fail(format!("{err}, there is no config file")),
+    })
+}
+
+/// Use the settings of the config file for the options that were not
+/// given on the command line. Settings for playing only are ignored in
+/// assist mode.
+fn apply_settings(args: &mut Args, matches: &ArgMatches, settings: Settings) -> Result<(), String> {
+    let unset = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+
+    if let (true, Some(mode)) = (unset("mode"), settings.mode) {
+        args.mode = parse_setting("mode", &mode)?;
+    }
+    if let (true, Some(dict)) = (unset("dict"), settings.dict) {
+        args.dict = dict;
+    }
+    if let (true, Some(word_length)) = (unset("word_length"), settings.word_length) {
+        args.word_length = word_length;
+    }
+    if let (true, Some(max_attempts)) = (unset("max_attempts"), settings.max_attempts) {
+        args.max_attempts = max_attempts;
+    }
+    if let (true, Some(theme)) = (unset("theme"), settings.theme) {
+        args.theme = theme;
+    }
+    if let (true, Some(palette)) = (unset("palette"), settings.palette) {
+        args.palette = Some(palette);
+    }
+    if let (true, Some(layout)) = (unset("layout"), settings.layout) {
+        args.layout = layout;
+    }
+    if let (true, Some(layout_file)) = (unset("layout_file"), settings.layout_file) {
+        args.layout_file = Some(layout_file);
+    }
+    if let (true, Some(reduced_motion)) = (unset("reduced_motion"), settings.reduced_motion) {
+        args.reduced_motion = reduced_motion;
+    }
+    // the same options would be rejected when assisting
+    if args.mode == Mode::Play {
+        if let (true, Some(answers)) = (unset("answers"), settings.answers) {
+            args.answers = Some(answers);
+        }
+        if let (true, Some(hard)) = (unset("hard"), settings.hard) {
+            args.hard = hard;
+        }
+        // a fixed or daily target does not depend on the difficulty
+        if let (true, Some(level)) = (unset("difficulty"), settings.difficulty) {
+            if !args.daily && args.target.is_none() {
+                args.difficulty = Some(parse_setting("difficulty", &level)?);
+            }
+        }
+        if let (true, Some(countdown)) = (unset("countdown"), settings.countdown) {
+            args.countdown = Some(countdown);
+        }
+        if let (true, Some(stopwatch)) = (unset("stopwatch"), settings.stopwatch) {
+            args.stopwatch = stopwatch;
+        }
+        if let (true, Some(series)) = (unset("series"), settings.series) {
+            args.series = Some(series);
+        }
+        if let (true, Some(interface)) = (unset("interface"), settings.interface) {
+            args.interface = parse_setting("interface", &interface)?;
+        }
+    }
+    if let (true, Some(style)) = (unset("share_style"), settings.share_style) {
+        args.share_style = parse_setting("share-style", &style)?;
+    }
+    Ok(())
+}
+
+/// What the help screen shows: the rules of the mode, its keys and the
+/// settings in use.
+fn help_info(args: &Args, keys: &KeyBindings, palette: Palette) -> HelpInfo {
+    let on_off = |on: bool| if on { "on" } else { "off" }.to_string();
+
+    let mut settings = vec![("mode", value_name(args.mode))];
+    if let Some(profile) = &args.profile {
+        settings.push(("profile", profile.clone()));
+    }
+    settings.push(("dictionary", args.dict.display().to_string()));
+    settings.push(("word length", args.word_length.to_string()));
+    if args.mode == Mode::Play {
+        let answers = match &args.answers {
+            Some(answers) => answers.display().to_string(),
+            None => "the whole dictionary".to_string(),
+        };
+        // a fixed target is not given away
+        let target = match (args.daily, &args.target, args.seed) {
+            (true, _, _) => "today's puzzle".to_string(),
+            (_, Some(_), _) => "fixed".to_string(),
+            (_, _, Some(seed)) => format!("random, seed {seed}"),
+            _ => "random".to_string(),
+        };
+        let difficulty = match args.difficulty {
+            Some(level) => value_name(level),
+            None => "any".to_string(),
+        };
+        settings.push(("answers", answers));
+        settings.push(("attempts", args.max_attempts.to_string()));
+        settings.push(("hard mode", on_off(args.hard)));
+        settings.push(("target", target));
+        settings.push(("difficulty", difficulty));
+        let clock = match (args.countdown, args.stopwatch, args.series) {
+            (Some(secs), _, _) => format!("countdown, {secs} seconds per game"),
+            (None, true, Some(series)) => format!("stopwatch, series of {series} games"),
+            (None, true, None) => "stopwatch".to_string(),
+            (None, false, _) => "off".to_string(),
+        };
+        settings.push(("clock", clock));
+        settings.push(("animations", on_off(!args.reduced_motion)));
+    }
+    settings.push(("layout", args.layout.clone()));
+    let palette_name = match &args.palette {
+        Some(palette) => palette.clone(),
+        None => "from the theme".to_string(),
+    };
+    settings.push(("palette", palette_name));
+    settings.push(("theme", args.theme.display().to_string()));
+
+    HelpInfo {
+        assist: args.mode == Mode::Assist,
+        keys: keys.clone(),
+        palette,
+        word_length: args.word_length,
+        max_attempts: args.max_attempts,
+        hard_mode: args.hard,
+        countdown: args.countdown,
+        settings,
+    }
+}
+
+/// Name of the value of an option, as it is given on the command line.
+fn value_name<T: ValueEnum>(value: T) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Parse the value of a setting of the config file like the matching option.
+fn parse_setting<T: ValueEnum>(name: &str, value: &str) -> Result<T, String> {
+    T::from_str(value, true).map_err(|_| {
+        let expected: Vec<String> = T::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect();
+        format!(
+            "invalid {name} {value} in config, expected one of: {}",
+            expected.join(", ")
+        )
+    })
+}
+
+/// Check the combinations of arguments that clap cannot express.
+fn validate_args(args: &Args) -> Result<(), String> {
+    if args.word_length == 0 {
+        return Err("--word-length must be at least 1".to_string());
+    }
+    if args.max_attempts == 0 {
+        return Err("--max-attempts must be at least 1".to_string());
+    }
+    if args.mode == Mode::Assist {
+        let play_only = [
+            ("--answers", args.answers.is_some()),
+            ("--seed", args.seed.is_some()),
+            ("--daily", args.daily),
+            ("--hard", args.hard),
+            ("--target", args.target.is_some()),
+            ("--difficulty", args.difficulty.is_some()),
+            ("--countdown", args.countdown.is_some()),
+            ("--stopwatch", args.stopwatch),
+            ("--series", args.series.is_some()),
+            ("--protocol", args.protocol.is_some()),
+        ];
+        if let Some((flag, _)) = play_only.iter().find(|(_, set)| *set) {
+            return Err(format!("{flag} can only be used with --mode play"));
+        }
+        if args.interface != Interface::Tui {
+            return Err(format!(
+                "--interface {} can only be used with --mode play",
+                value_name(args.interface)
+            ));
+        }
+        if args.word_length > pattern::MAX_CODE_WORD_LENGTH {
+            return Err(format!(
+                "--mode assist supports words of up to {} letters",
+                pattern::MAX_CODE_WORD_LENGTH
+            ));
+        }
+    }
+    if args.countdown == Some(0) {
+        return Err("--countdown must be at least 1 second".to_string());
+    }
+    if args.countdown.is_some() && args.stopwatch {
+        return Err("--countdown cannot be used with --stopwatch".to_string());
+    }
+    match args.series {
+        Some(_) if !args.stopwatch => {
+            return Err("--series can only be used with --stopwatch".to_string())
+        }
+        Some(0) => return Err("--series must be at least 1 game".to_string()),
+        _ => (),
+    }
+    if let Some(target) = &args.target {
+        if target.chars().count() != args.word_length {
+            return Err(format!(
+                "--target {target} must have {} letters",
+                args.word_length
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Set up the targets of a session from the arguments, and its first game.
+/// Exits with an error message if there is nothing to pick from.
+fn new_session(args: &Args, dict: Dictionary) -> Rc<Session> {
+    let answers = match &args.answers {
+        Some(path) => targets::load_answers(path, &dict).unwrap_or_else(|err| {
+            fail(format!("could not load answers {}: {err}", path.display()))
+        }),
+        None => dict.sorted_words(),
+    };
+    if answers.is_empty() {
+        fail("there are no answers to pick targets from");
+    }
+    let answers = match args.difficulty.map(Difficulty::from) {
+        Some(level) => {
+            let rated = targets::with_difficulty(&dict, &answers, level);
+            if rated.is_empty() {
+                eprintln!("no {level} words among the answers, picking any word");
+                answers
+            } else {
+                rated
+            }
+        }
+        None => answers,
+    };
+    let mut picker = TargetPicker::new(answers, args.seed);
+
+    let today = Local::now().date_naive();
+    let (target_word, mode) = if args.daily {
+        (picker.daily(today), "daily")
+    } else if let Some(target) = &args.target {
+        if !dict.contains(target) {
+            fail(format!("--target {target} is not in the dictionary"));
+        }
+        (target.clone(), "play")
+    } else {
+        (picker.pick(), "play")
+    };
+
+    let clock = match (args.countdown, args.stopwatch) {
+        (Some(secs), _) => Clock::Countdown(Duration::from_secs(secs)),
+        (None, true) => Clock::Stopwatch {
+            series: args.series,
+        },
+        (None, false) => Clock::Off,
+    };
+
+    let mut game = Game::new(dict, &target_word, args.max_attempts);
+    game.set_hard_mode(args.hard);
+    if let Clock::Countdown(limit) = clock {
+        game.set_time_limit(Some(limit));
+    }
+    Rc::new(Session {
+        // wrap the game into a shared Rc, so that we can pass it to the
+        // closure for cursive event handlers. Dynamic Borrowing!
+        game: Rc::new(RefCell::new(game)),
+        targets: RefCell::new(picker),
+        mode: Cell::new(clock.mode().unwrap_or(mode)),
+        puzzle: Cell::new(args.daily.then(|| share::puzzle_number(today))),
+        clock,
+        series: Rc::new(RefCell::new(vec![])),
+        stats: StatsStore::open_default(),
+    })
+}
+
+/// Set up the board for the games of a session.
+fn setup_game(
+    siv: &mut Cursive,
+    session: Rc<Session>,
+    layout: KeyboardLayout,
+    palette: Palette,
+    motion: bool,
+    keys: KeyBindings,
+) {
+    let new_game: NewGame = {
+        let session = session.clone();
+        Rc::new(move |s| start_new_round(s, &session))
+    };
+
+    let give_up = {
+        let session = session.clone();
+        let new_game = new_game.clone();
+        move |s: &mut Cursive| confirm_give_up(s, session.clone(), new_game.clone())
+    };
+
+    let replayer = Rc::new(Replayer {
+        dict: session.game.borrow().dictionary().clone(),
+        max_guesses: session.game.borrow().max_guesses(),
+        layout: layout.clone(),
+        palette,
+    });
+
+    // the clock, if any, goes left of the messages so that it does not move
+    let mut status = LinearLayout::horizontal();
+    let timed = session.clock != Clock::Off;
+    if timed {
+        let session = session.clone();
+        let new_game = new_game.clone();
+        status.add_child(ClockView::new(
+            session.game.clone(),
+            session.clock,
+            session.series.clone(),
+            move |s| finish_game(s, &session, new_game.clone()),
+        ));
+    }
+    status.add_child(StatusLine::new());
+
+    // Custom View to draw the wordle board, which guesses are typed into
+    let gameboard = BoardView::new(session.game.clone(), layout)
+        .with_palette(palette)
+        .with_animations(motion)
+        .on_submit(move |s, guess| process_guess(s, guess, &session, &new_game))
+        .with_name("board");
+
+    let main_panel = LinearLayout::vertical()
+        .child(gameboard)
+        .child(status)
+        .child(TextView::new(format!(
+            "  {}: statistics   {}: give up   {}: messages   {}: history   {}: help",
+            keys.statistics.name,
+            keys.give_up.name,
+            keys.messages.name,
+            keys.history.name,
+            keys.help.name
+        )));
+
+    let view = LinearLayout::vertical().child(main_panel);
+    siv.add_layer(view);
+    siv.add_global_callback(keys.statistics.event, |s| stats_screen::show(s, None));
+    siv.add_global_callback(keys.give_up.event, give_up);
+    siv.add_global_callback(keys.messages.event, status_line::show_history);
+    siv.add_global_callback(keys.history.event, move |s| {
+        history_screen::show(s, replayer.clone())
+    });
+    if timed {
+        clock::start(siv);
+    }
+}
+
+/// Start the next round with a random target word, keeping the session.
+/// Closes the dialogs over the board and clears the guess box.
+fn start_new_round(s: &mut Cursive, session: &Session) {
+    session.next_round();
+
+    while s.screen().len() > 1 {
+        s.pop_layer();
+    }
+    s.call_on_name("board", |view: &mut BoardView| view.reset());
+    s.with_user_data(|settings: &mut ShareSettings| settings.text = None);
+    let _ = s.focus_name("board");
+}
+
+/// Ask before giving up, which reveals the answer and counts as a loss.
+fn confirm_give_up(s: &mut Cursive, session: Rc<Session>, new_game: NewGame) {
+    // only while guessing, not over the end of game dialogs
+    if session.game.borrow().state != GameState::Playing || s.screen().len() > 1 {
+        return;
+    }
+    s.add_layer(
+        Dialog::text("Give up and reveal the answer?\nThis counts as a loss.")
+            .title("Give up")
+            .button("Give up", move |s| {
+                s.pop_layer();
+                session.game.borrow_mut().give_up();
+                finish_game(s, &session, new_game.clone());
+            })
+            .button("Keep playing", |s| {
+                s.pop_layer();
+            }),
+    );
+}
+
+/// Given a guess, submit it to the game and process the result.
+/// Returns whether the guess was accepted.
+fn process_guess(s: &mut Cursive, guess: &str, session: &Rc<Session>, new_game: &NewGame) -> bool {
+    let submitted = session.game.borrow_mut().submit(guess);
+    let rejected = match submitted {
+        Ok(_) => None,
+        // a guess that comes too late ends the game rather than being checked
+        Err(SubmitError::OutOfTime) => {
+            finish_game(s, session, new_game.clone());
+            return false;
+        }
+        Err(SubmitError::GameOver) => return false,
+        Err(SubmitError::WrongLength { letters, expected }) if letters < expected => {
+            Some("not enough letters".to_string())
+        }
+        Err(SubmitError::WrongLength { .. }) => Some("too many letters".to_string()),
+        Err(SubmitError::Invalid(GuessError::NotInDictionary)) => {
+            Some(format!("{guess}: not in word list"))
+        }
+        Err(SubmitError::Invalid(err)) => Some(format!("hard mode: {err}")),
+    };
+    if let Some(message) = rejected {
+        animate_board(s, BoardView::shake);
+        status_line::show(s, message);
+        return false;
+    }
+
+    let (won, finished) = {
+        let game = session.game.borrow();
+        (
+            game.state == GameState::Win,
+            game.state != GameState::Playing,
+        )
+    };
+    let revealing = animate_board(s, |board| board.reveal_guess(won));
+    if finished {
+        // answer found, or attempts exhausted, shown once the guess is revealed
+        let (session, new_game) = (session.clone(), new_game.clone());
+        let finish = move |s: &mut Cursive| finish_game(s, &session, new_game);
+        if revealing {
+            s.call_on_name("board", |board: &mut BoardView| {
+                board.after_animations(finish)
+            });
+        } else {
+            finish(s);
+        }
+    }
+    true
+}
+
+/// Start an animation of the board, and draw it until it is over.
+/// Returns false if animations are off.
+fn animate_board(s: &mut Cursive, animate: impl FnOnce(&mut BoardView) -> bool) -> bool {
+    let animating = s.call_on_name("board", animate).unwrap_or(false);
+    if animating {
+        board_view::animate(s, "board");
+    }
+    animating
+}
+
+/// Record a finished game and show how it ended.
+fn finish_game(s: &mut Cursive, session: &Session, new_game: NewGame) {
+    if let Err(err) = session.record_game() {
+        s.add_layer(Dialog::info(format!("Could not save statistics: {err}")));
+    }
+    let game = session.game.borrow();
+    share_screen::finish_game(s, &game, session.puzzle.get());
+    let mut details = difficulty_message(game.target_difficulty());
+    details.append(time_message(session, &game));
+    match game.state {
+        GameState::Win => win(s, details, new_game),
+        GameState::Lose => lose(s, "You Lost! 😣", &game.target_word, details, new_game),
+        GameState::OutOfTime => lose(s, "Out of time! ⌛", &game.target_word, details, new_game),
+        GameState::Playing => (),
+    }
+}
+
+/// Time taken by a finished game, and by the series it ends, if the games
+/// are timed. Starts a new series once one is complete.
+fn time_message(session: &Session, game: &Game) -> StyledString {
+    let time = clock::format_duration(game.duration());
+    let message = match session.clock {
+        Clock::Off => return StyledString::new(),
+        Clock::Countdown(limit) => format!("\n\tTime: {time} of {}", clock::format_duration(limit)),
+        Clock::Stopwatch { series: None } => format!("\n\tTime: {time}"),
+        Clock::Stopwatch {
+            series: Some(length),
+        } => {
+            let mut series = session.series.borrow_mut();
+            series.push(game.duration());
+            let total = clock::format_duration(series.iter().sum());
+            let message = format!(
+                "\n\tTime: {time}\n\tSeries: {} of {length} games, {total} in total",
+                series.len()
+            );
+            if series.len() == length {
+                series.clear();
+            }
+            message
+        }
+    };
+    StyledString::plain(message)
+}
+
+/// Show message after the user wins.
+fn win(s: &mut Cursive, details: StyledString, new_game: NewGame) {
+    let mut message = StyledString::plain("\n\n");
+    message.append(StyledString::styled(
+        "\tYou Win! 🥳",
+        Style::from(Color::Light(BaseColor::Green)).combine(Effect::Bold),
+    ));
+    message.append(details);
+    s.add_layer(
+        Dialog::around(TextView::new(message))
+            .title("Congratulations!")
+            .button("New game", {
+                let new_game = new_game.clone();
+                move |s| new_game(s)
+            })
+            .button("Share", share_screen::show)
+            .button("Ok", move |s| show_final_stats(s, new_game.clone())),
+    );
+}
+
+/// Show message after the user loses, with the reason as its headline.
+fn lose(
+    s: &mut Cursive,
+    headline: &str,
+    correct_word: &str,
+    details: StyledString,
+    new_game: NewGame,
+) {
+    let mut message = StyledString::plain("\n\n");
+    message.append(StyledString::styled(
+        format!("\t{headline}\n Better Luck next time."),
+        Style::from(Color::Light(BaseColor::Cyan)).combine(Effect::Bold),
+    ));
+    message.append(StyledString::styled(
+        format!("\n\tAnswer: {correct_word}"),
+        Style::from(Color::Light(BaseColor::Blue)).combine(Effect::Bold),
+    ));
+    message.append(details);
+
+    s.add_layer(
+        Dialog::around(TextView::new(message))
+            .title("Oh no!")
+            .button("New game", {
+                let new_game = new_game.clone();
+                move |s| new_game(s)
+            })
+            .button("Share", share_screen::show)
+            .button("Ok", move |s| show_final_stats(s, new_game.clone())),
+    );
+}
+
+/// Replace the end of game dialog with the statistics.
+fn show_final_stats(s: &mut Cursive, new_game: NewGame) {
+    s.pop_layer();
+    stats_screen::show(s, Some(new_game));
+}
+
+/// Describe how hard the target word was, if it is known.
+fn difficulty_message(difficulty: Option<DifficultyRating>) -> StyledString {
+    match difficulty {
+        Some(rating) => StyledString::plain(format!(
+            "\n\tDifficulty: {} (solver needs {} guesses)",
+            rating.level(),
+            rating.solver_guesses
+        )),
+        None => StyledString::new(),
+    }
+}
+
+/// Load dictionary from a file.
+/// Exits with an error message if it cannot be read, or has no words.
+fn load_dict(path: &Path, word_length: usize) -> Dictionary {
+    eprintln!("loading dict from path: {}", path.display());
+
+    let dict = Dictionary::load(path, word_length).unwrap_or_else(|err| {
+        fail(format!(
+            "could not load dictionary {}: {err}",
+            path.display()
+        ))
+    });
+    if dict.is_empty() {
+        fail(format!("dictionary {} has no words", path.display()));
+    }
+    dict
+}
+
+/// Find the keyboard layout by name, among the presets and the layouts file.
+/// A missing layouts file is only an error if it was asked for explicitly.
+/// Exits with an error message if the layout cannot be found.
+fn load_layout(name: &str, layout_file: Option<&Path>) -> KeyboardLayout {
+    let default_file = dirs::config_dir().map(|dir| dir.join("wordle-star").join("layouts.toml"));
+    let path = match (layout_file, &default_file) {
+        (Some(path), _) => Some(path),
+        (None, Some(path)) if path.exists() => Some(path.as_path()),
+        _ => None,
+    };
+
+    let custom = match path {
+        None => vec![],
+        Some(path) => {
+            let parsed = fs::read_to_string(path)
+                .map_err(|err| err.to_string())
+                .and_then(|contents| {
+                    KeyboardLayout::parse_layouts(&contents).map_err(|err| err.to_string())
+                });
+            parsed.unwrap_or_else(|err| {
+                fail(format!(
+                    "could not load layouts from {}: {err}",
+                    path.display()
+                ))
+            })
+        }
+    };
+
+    KeyboardLayout::find(name, &custom).unwrap_or_else(|| {
+        let mut names: Vec<&str> = keyboard_layout::PRESETS.to_vec();
+        names.extend(custom.iter().map(|layout| layout.name.as_str()));
+        fail(format!(
+            "unknown layout {name}, expected one of: {}",
+            names.join(", ")
+        ))
+    })
+}
+
+/// Load the theme with the colours of the interface.
+/// Exits with an error message if it cannot be read.
+fn load_theme(path: &Path) -> Theme {
+    theme::load_theme_file(path).unwrap_or_else(|err| {
+        let reason = match err {
+            theme::Error::Io(err) => err.to_string(),
+            theme::Error::Parse(err) => err.to_string(),
+        };
+        fail(format!("could not load theme {}: {reason}", path.display()))
+    })
+}
+
+/// Load the palette of the tiles, by name or from the theme file.
+/// Exits with an error message if it cannot be found.
+fn load_palette(theme_path: &Path, name: Option<&str>) -> Palette {
+    if let Some(name) = name {
+        if Palette::preset(name).is_none() {
+            fail(format!(
+                "unknown palette {name}, expected one of: {}",
+                palette::PRESETS.join(", ")
+            ));
+        }
+    }
+    // the theme has already been read, so it is there
+    let contents = fs::read_to_string(theme_path).unwrap_or_default();
+    Palette::from_theme(&contents, name).unwrap_or_else(|err| {
+        fail(format!(
+            "could not load palette from {}: {err}",
+            theme_path.display()
+        ))
+    })
+}
+
+/// Load the difficulty ratings saved next to the dictionary, if any.
+/// Without them, targets are picked regardless of difficulty.
+fn load_difficulty(dict: &mut Dictionary, dict_path: &Path) {
+    let path = difficulty::metadata_path(dict_path);
+    if !path.exists() {
+        return;
+    }
+    if let Err(err) = dict.load_difficulty(&path) {
+        eprintln!("could not load difficulty ratings: {err}");
+    }
+}
+
+/// Set up a solver for the dictionary. The pattern matrix is memory-mapped
+/// from the cache, or built and cached on the first run. The matrix is
+/// used even if it cannot be cached.
+fn load_solver(dict: &Dictionary) -> Solver {
+    eprintln!("loading pattern matrix");
+    let (solver, cached) = Solver::with_cached_matrix(dict.sorted_words());
+    if let Err(err) = cached {
+        eprintln!("could not cache pattern matrix: {err}");
+    }
+    solver
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    /// A session of games in three tries, all with the target cat, which
+    /// are not recorded.
+    pub fn session() -> Session {
+        let mut dict = Dictionary::new(3);
+        for word in ["cat", "bat", "rat", "tab"] {
+            dict.add_word_str(word);
+        }
+        Session {
+            game: Rc::new(RefCell::new(Game::new(dict, "cat", 3))),
+            targets: RefCell::new(TargetPicker::new(vec!["cat".to_string()], None)),
+            mode: Cell::new("play"),
+            puzzle: Cell::new(None),
+            clock: Clock::Off,
+            series: Rc::new(RefCell::new(vec![])),
+            stats: None,
+        }
+    }
+}
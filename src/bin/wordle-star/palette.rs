@@ -0,0 +1,228 @@
+use cursive::theme::{BaseColor, Color, ColorStyle, Effect, Style};
+use wordle_star::guess::CharState;
+
+/// Names of the built in palettes.
+pub const PRESETS: [&str; 4] = ["classic", "colour-blind", "high-contrast", "monochrome"];
+
+/// How a tile, or a key of the on-screen keyboard, is drawn.
+#[derive(Clone, Copy)]
+pub struct TileStyle {
+    pub style: Style,
+
+    /// drawn under the tile, for palettes that do not rely on colour
+    pub marker: Option<char>,
+}
+
+impl TileStyle {
+    fn new(fg: Color, bg: Color) -> TileStyle {
+        TileStyle {
+            style: Style::from(ColorStyle::new(fg, bg)),
+            marker: None,
+        }
+    }
+
+    fn with_effect(mut self, effect: Effect) -> TileStyle {
+        self.style = self.style.combine(effect);
+        self
+    }
+
+    fn with_marker(mut self, marker: char) -> TileStyle {
+        self.marker = Some(marker);
+        self
+    }
+}
+
+/// Colours of the tiles of each state, used everywhere tiles are drawn.
+#[derive(Clone, Copy)]
+pub struct Palette {
+    pub absent: TileStyle,
+    pub present: TileStyle,
+    pub correct: TileStyle,
+
+    /// keys that have not been guessed yet
+    pub unused: TileStyle,
+}
+
+impl Palette {
+    /// One of the built in palettes, see `PRESETS`.
+    pub fn preset(name: &str) -> Option<Palette> {
+        let black = Color::Dark(BaseColor::Black);
+        let white = Color::Dark(BaseColor::White);
+        let grey = Color::Light(BaseColor::Black);
+        let unused = TileStyle::new(white, black);
+        let palette = match name {
+            "classic" => Palette {
+                absent: TileStyle::new(white, grey),
+                present: TileStyle::new(black, Color::Dark(BaseColor::Yellow)),
+                correct: TileStyle::new(black, Color::Dark(BaseColor::Green)),
+                unused,
+            },
+            // orange and blue, as in the high contrast mode of the original game
+            "colour-blind" => Palette {
+                absent: TileStyle::new(white, grey),
+                present: TileStyle::new(black, Color::Rgb(133, 192, 249)),
+                correct: TileStyle::new(black, Color::Rgb(245, 121, 58)),
+                unused,
+            },
+            "high-contrast" => Palette {
+                absent: TileStyle::new(Color::Light(BaseColor::White), black),
+                present: TileStyle::new(black, Color::Light(BaseColor::Yellow))
+                    .with_effect(Effect::Bold),
+                correct: TileStyle::new(black, Color::Light(BaseColor::Green))
+                    .with_effect(Effect::Bold),
+                unused: TileStyle::new(Color::Light(BaseColor::White), black)
+                    .with_effect(Effect::Underline),
+            },
+            // states differ by the marker under each tile, and by effects
+            "monochrome" => Palette {
+                absent: TileStyle::new(grey, black).with_marker('-'),
+                present: TileStyle::new(white, black)
+                    .with_effect(Effect::Underline)
+                    .with_marker('+'),
+                correct: TileStyle::new(black, white)
+                    .with_effect(Effect::Bold)
+                    .with_marker('*'),
+                unused,
+            },
+            _ => return None,
+        };
+        Some(palette)
+    }
+
+    /// Style of a tile, or of a key that has not been guessed if None.
+    pub fn tile(&self, state: Option<CharState>) -> TileStyle {
+        match state {
+            None => self.unused,
+            Some(CharState::NotFound) => self.absent,
+            Some(CharState::IncorrectPosition) => self.present,
+            Some(CharState::CorrectPosition) => self.correct,
+        }
+    }
+
+    /// Use the settings of the `[palette]` table of a theme file: the name of
+    /// a preset, used if none was given otherwise, and background colours of
+    /// the tiles in the cursive format, e.g.
+    ///
+    /// ```toml
+    /// [palette]
+    /// preset = "colour-blind"
+    /// correct = "#f5793a"
+    /// present = "light blue"
+    /// absent = "light black"
+    /// ```
+    pub fn from_theme(contents: &str, name: Option<&str>) -> Result<Palette, String> {
+        let theme: toml::Table = toml::from_str(contents).map_err(|err| err.to_string())?;
+        let table = match theme.get("palette") {
+            None => toml::Table::new(),
+            Some(toml::Value::Table(table)) => table.clone(),
+            Some(_) => return Err("palette must be a table".to_string()),
+        };
+
+        let setting = |key: &str| match table.get(key) {
+            None => Ok(None),
+            Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+            Some(_) => Err(format!("palette {key} must be a string")),
+        };
+        let preset = match name {
+            Some(name) => name.to_string(),
+            None => setting("preset")?.unwrap_or_else(|| "classic".to_string()),
+        };
+        let mut palette = Palette::preset(&preset).ok_or_else(|| {
+            format!(
+                "unknown palette {preset}, expected one of: {}",
+                PRESETS.join(", ")
+            )
+        })?;
+
+        for (key, tile) in [
+            ("absent", &mut palette.absent),
+            ("present", &mut palette.present),
+            ("correct", &mut palette.correct),
+        ] {
+            if let Some(value) = setting(key)? {
+                let color = Color::parse(&value)
+                    .ok_or_else(|| format!("invalid colour {value} for palette {key}"))?;
+                let mut colors = tile.style.color;
+                colors.back = color.into();
+                tile.style.color = colors;
+            }
+        }
+        for key in table.keys() {
+            if !["preset", "absent", "present", "correct"].contains(&key.as_str()) {
+                return Err(format!("unknown palette setting {key}"));
+            }
+        }
+        Ok(palette)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::preset("classic").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cursive::theme::ColorType;
+
+    fn background(tile: TileStyle) -> ColorType {
+        tile.style.color.back
+    }
+
+    #[test]
+    fn test_preset_from_theme() {
+        let classic = Palette::from_theme("", None).unwrap();
+        assert_eq!(
+            ColorType::from(Color::Dark(BaseColor::Green)),
+            background(classic.correct)
+        );
+
+        let theme = "[palette]\npreset = \"monochrome\"\n";
+        let palette = Palette::from_theme(theme, None).unwrap();
+        assert_eq!(Some('*'), palette.correct.marker);
+
+        // a palette given as an option overrides the one of the theme
+        let palette = Palette::from_theme(theme, Some("classic")).unwrap();
+        assert_eq!(None, palette.correct.marker);
+    }
+
+    #[test]
+    fn test_colour_from_theme() {
+        let theme = "[palette]\npreset = \"colour-blind\"\ncorrect = \"#ff0000\"\n";
+        let palette = Palette::from_theme(theme, None).unwrap();
+        assert_eq!(
+            ColorType::from(Color::Rgb(255, 0, 0)),
+            background(palette.correct)
+        );
+        assert_eq!(
+            ColorType::from(Color::Rgb(133, 192, 249)),
+            background(palette.present)
+        );
+    }
+
+    #[test]
+    fn test_invalid_theme() {
+        let error = |theme: &str| Palette::from_theme(theme, None).err().unwrap();
+        assert_eq!("palette must be a table", error("palette = \"classic\""));
+        assert_eq!(
+            "palette correct must be a string",
+            error("[palette]\ncorrect = 3")
+        );
+        assert_eq!(
+            "invalid colour blurple for palette absent",
+            error("[palette]\nabsent = \"blurple\"")
+        );
+        assert_eq!(
+            "unknown palette setting unused",
+            error("[palette]\nunused = \"red\"")
+        );
+        assert_eq!(
+            "unknown palette sepia, expected one of: classic, colour-blind, \
+             high-contrast, monochrome",
+            error("[palette]\npreset = \"sepia\"")
+        );
+        assert!(!error("[palette").is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use wordle_star::game::{GameState, GuessError, SubmitError};
+use wordle_star::guess::{CharState, GuessResult};
+
+use crate::clock::{self, Clock};
+use crate::Session;
+
+/// Play line by line on stdin and stdout, without taking over the
+/// terminal, so that games can be played on dumb terminals or scripted.
+/// Each guess is printed as a row of tiles, in colour unless NO_COLOR is
+/// set or the terminal is dumb, in which case each letter is followed by
+/// a marker: * correct, + elsewhere, - absent. A new game starts as soon
+/// as one is over, until the input ends or /quit is typed.
+pub fn run(session: &Session) -> io::Result<()> {
+    let no_colour = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+    let stdin = io::stdin();
+    // no prompts in the output of piped games
+    let prompt = stdin.is_terminal();
+    play(
+        session,
+        stdin.lock(),
+        &mut io::stdout(),
+        !no_colour && !dumb,
+        prompt,
+    )
+}
+
+fn play(
+    session: &Session,
+    input: impl BufRead,
+    out: &mut impl Write,
+    colour: bool,
+    prompt: bool,
+) -> io::Result<()> {
+    start(session, out)?;
+    if prompt {
+        show_prompt(session, out)?;
+    }
+    for line in input.lines() {
+        let line = line?.trim().to_lowercase();
+        match line.as_str() {
+            "" => (),
+            "/quit" => break,
+            "/give-up" => give_up(session, out)?,
+            command if command.starts_with('/') => {
+                writeln!(out, "unknown command {command}, expected /give-up or /quit")?;
+            }
+            guess => submit(session, guess, out, colour)?,
+        }
+        if prompt {
+            show_prompt(session, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn start(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    write!(
+        out,
+        "guess the {} letter word in {} tries",
+        game.word_length(),
+        game.max_guesses()
+    )?;
+    if game.hard_mode() {
+        write!(out, ", hard mode")?;
+    }
+    if let Clock::Countdown(limit) = session.clock {
+        write!(out, ", within {}", clock::format_duration(limit))?;
+    }
+    writeln!(out)
+}
+
+fn show_prompt(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    let game = session.game.borrow();
+    write!(
+        out,
+        "{}/{}> ",
+        game.guess_results.len() + 1,
+        game.max_guesses()
+    )?;
+    out.flush()
+}
+
+fn submit(session: &Session, guess: &str, out: &mut impl Write, colour: bool) -> io::Result<()> {
+    let submitted = session.game.borrow_mut().submit(guess);
+    let result = match submitted {
+        Ok(result) => result,
+        // games that are over are replaced by the next one right away
+        Err(SubmitError::OutOfTime | SubmitError::GameOver) => return finish(session, out),
+        Err(SubmitError::WrongLength { expected, .. }) => {
+            return writeln!(out, "{guess}: not {expected} letters")
+        }
+        Err(SubmitError::Invalid(GuessError::NotInDictionary)) => {
+            return writeln!(out, "{guess}: not in word list")
+        }
+        Err(SubmitError::Invalid(err)) => return writeln!(out, "hard mode: {err}"),
+    };
+    let game = session.game.borrow();
+    writeln!(
+        out,
+        "{}/{}  {}",
+        game.guess_results.len(),
+        game.max_guesses(),
+        tiles(&result, colour)
+    )?;
+    if game.state == GameState::Playing {
+        return Ok(());
+    }
+    drop(game);
+    finish(session, out)
+}
+
+/// Lose the game, unless its time ran out already.
+fn give_up(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    {
+        let mut game = session.game.borrow_mut();
+        game.check_time();
+        if game.state == GameState::Playing {
+            game.give_up();
+        }
+    }
+    finish(session, out)
+}
+
+/// Say how the game ended, record it in the stats and start the next one.
+fn finish(session: &Session, out: &mut impl Write) -> io::Result<()> {
+    {
+        let game = session.game.borrow();
+        let target = &game.target_word;
+        match game.state {
+            GameState::Win => writeln!(
+                out,
+                "solved in {}/{}: {target}",
+                game.guess_results.len(),
+                game.max_guesses()
+            )?,
+            GameState::Lose => writeln!(out, "lost: {target}")?,
+            GameState::OutOfTime => writeln!(out, "out of time: {target}")?,
+            GameState::Playing => return Ok(()),
+        }
+        if session.clock != Clock::Off {
+            writeln!(out, "time {}", clock::format_duration(game.duration()))?;
+        }
+    }
+    if let Err(err) = session.record_game() {
+        eprintln!("could not save statistics: {err}");
+    }
+    session.next_round();
+    writeln!(out)?;
+    start(session, out)
+}
+
+/// A guess as a row of tiles: the letters on the colours of the board, or
+/// each letter followed by the marker of its state.
+fn tiles(result: &GuessResult, colour: bool) -> String {
+    let tiles: Vec<String> = result
+        .char_guesses
+        .iter()
+        .map(|&(ch, state)| {
+            let ch = ch.to_uppercase();
+            if colour {
+                // bold white, or black, on green, yellow or grey
+                let code = match state {
+                    CharState::CorrectPosition => "1;30;42",
+                    CharState::IncorrectPosition => "1;30;43",
+                    CharState::NotFound => "1;97;100",
+                };
+                format!("\x1b[{code}m {ch} \x1b[0m")
+            } else {
+                let marker = match state {
+                    CharState::CorrectPosition => '*',
+                    CharState::IncorrectPosition => '+',
+                    CharState::NotFound => '-',
+                };
+                format!("{ch}{marker}")
+            }
+        })
+        .collect();
+    tiles.join(if colour { "" } else { " " })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::session;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    fn play_lines(lines: &str) -> String {
+        play_session(&session(), lines)
+    }
+
+    fn play_session(session: &Session, lines: &str) -> String {
+        let mut out = vec![];
+        play(session, Cursor::new(lines), &mut out, false, false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_guesses() {
+        assert_eq!(
+            "guess the 3 letter word in 3 tries\n\
+             1/3  T+ A* B-\n\
+             ca: not 3 letters\n\
+             cow: not in word list\n\
+             unknown command /help, expected /give-up or /quit\n\
+             2/3  C* A* T*\n\
+             solved in 2/3: cat\n\
+             \n\
+             guess the 3 letter word in 3 tries\n",
+            play_lines("tab\nca\nCOW\n/help\ncat\n")
+        );
+    }
+
+    #[test]
+    fn test_give_up_and_quit() {
+        assert_eq!(
+            "guess the 3 letter word in 3 tries\n\
+             1/3  B- A* T*\n\
+             lost: cat\n\
+             \n\
+             guess the 3 letter word in 3 tries\n\
+             1/3  R- A* T*\n",
+            play_lines("bat\n/give-up\n\nrat\n/quit\ncat\n")
+        );
+    }
+
+    #[test]
+    fn test_colour_tiles() {
+        let result = session().game.borrow_mut().guess_word("tab").unwrap();
+        assert_eq!(
+            "\x1b[1;30;43m T \x1b[0m\x1b[1;30;42m A \x1b[0m\x1b[1;97;100m B \x1b[0m",
+            tiles(&result, true)
+        );
+    }
+
+    #[test]
+    fn test_give_up_out_of_time() {
+        let session = Session {
+            clock: Clock::Countdown(Duration::ZERO),
+            ..session()
+        };
+        session
+            .game
+            .borrow_mut()
+            .set_time_limit(Some(Duration::ZERO));
+        assert_eq!(
+            "guess the 3 letter word in 3 tries, within 0:00\n\
+             out of time: cat\n\
+             time 0:00\n\
+             \n\
+             guess the 3 letter word in 3 tries, within 0:00\n",
+            play_session(&session, "/give-up\n")
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use cursive::views::{Dialog, TextView};
+use cursive::Cursive;
+
+use std::fs;
+use std::path::PathBuf;
+use wordle_star::game::Game;
+use wordle_star::share::{self, ShareStyle};
+
+use crate::status_line;
+
+/// How the result of the game is shared, kept as the user data of cursive.
+/// Once the game is over it also holds the share text, which is written to
+/// stdout when the program exits.
+pub struct ShareSettings {
+    pub style: ShareStyle,
+    pub hard_mode: bool,
+
+    /// file the share text is saved to when the game is over
+    pub file: Option<PathBuf>,
+    pub text: Option<String>,
+}
+
+/// Build the share text of a finished game, and save it if a file was given.
+/// Only the daily puzzle has a number.
+pub fn finish_game(s: &mut Cursive, game: &Game, puzzle: Option<i64>) {
+    let saved = s.with_user_data(|settings: &mut ShareSettings| {
+        let text = share::share_text(game, puzzle, settings.hard_mode, settings.style);
+        let saved = settings.file.as_ref().map(|file| {
+            fs::write(file, &text)
+                .map(|_| file.clone())
+                .map_err(|err| (file.clone(), err))
+        });
+        settings.text = Some(text);
+        saved
+    });
+    match saved {
+        Some(Some(Ok(file))) => status_line::show(s, format!("saved result to {}", file.display())),
+        Some(Some(Err((file, err)))) => s.add_layer(Dialog::info(format!(
+            "Could not save the result to {}: {err}",
+            file.display()
+        ))),
+        _ => (),
+    }
+}
+
+/// Show the share text of the finished game, ready to be copied.
+pub fn show(s: &mut Cursive) {
+    let message = s
+        .with_user_data(|settings: &mut ShareSettings| {
+            let mut message = settings.text.clone().unwrap_or_default();
+            if let Some(file) = &settings.file {
+                message.push_str(&format!("\n\nSaved to {}", file.display()));
+            }
+            message.push_str("\n\nIt will also be printed when you quit.");
+            message
+        })
+        .unwrap_or_default();
+    s.add_layer(
+        Dialog::around(TextView::new(message))
+            .title("Share")
+            .button("Back", |s| {
+                s.pop_layer();
+            }),
+    );
+}
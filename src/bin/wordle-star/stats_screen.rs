@@ -0,0 +1,79 @@
+use cursive::theme::*;
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, TextView};
+use cursive::Cursive;
+
+use std::rc::Rc;
+use wordle_star::stats::{Stats, StatsStore};
+
+/// Width of the bar of the most common number of guesses.
+const MAX_BAR_WIDTH: usize = 30;
+
+/// Callback that starts the next round.
+pub type NewGame = Rc<dyn Fn(&mut Cursive)>;
+
+/// Show the statistics of every game played so far. After a game, the next
+/// one can be started from here, otherwise the screen can be closed to keep
+/// playing.
+pub fn show(s: &mut Cursive, after_game: Option<NewGame>) {
+    // already showing
+    if s.find_name::<TextView>("stats").is_some() {
+        return;
+    }
+
+    let records = match StatsStore::open_default().map(|store| store.load()) {
+        Some(Ok(records)) => records,
+        Some(Err(err)) => {
+            s.add_layer(Dialog::info(format!("Could not load statistics: {err}")));
+            return;
+        }
+        None => vec![],
+    };
+    let stats = Stats::from_records(&records);
+
+    let dialog =
+        Dialog::around(TextView::new(stats_message(&stats)).with_name("stats")).title("Statistics");
+    let dialog = if let Some(new_game) = after_game {
+        dialog
+            .button("New game", move |s| new_game(s))
+            .button("Quit", |s| s.quit())
+    } else {
+        dialog.button("Back", |s| {
+            s.pop_layer();
+        })
+    };
+    s.add_layer(dialog);
+}
+
+/// Totals, streaks, and a histogram of the number of guesses per win.
+fn stats_message(stats: &Stats) -> StyledString {
+    let mut message = StyledString::plain(format!(
+        "\nPlayed: {}   Win %: {:.0}   Current streak: {}   Max streak: {}\n\n",
+        stats.played,
+        stats.win_percentage(),
+        stats.current_streak,
+        stats.max_streak
+    ));
+    message.append(StyledString::styled(
+        "Guess distribution\n",
+        Style::from(Effect::Bold),
+    ));
+
+    let most = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+    for (index, &count) in stats.distribution.iter().enumerate() {
+        // every count gets at least one block, so that it is visible
+        let width = if count == 0 {
+            0
+        } else {
+            (count * MAX_BAR_WIDTH / most).max(1)
+        };
+        message.append_plain(format!("{:>2} ", index + 1));
+        message.append(StyledString::styled(
+            "█".repeat(width),
+            Color::Dark(BaseColor::Green),
+        ));
+        message.append_plain(format!(" {count}\n"));
+    }
+    message
+}
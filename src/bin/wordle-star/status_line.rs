@@ -0,0 +1,114 @@
+use cursive::traits::*;
+use cursive::view::ScrollStrategy;
+use cursive::views::{Dialog, NamedView, ScrollView, TextView};
+use cursive::{Cursive, Printer, Vec2};
+
+use chrono::{DateTime, Local};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Time a message stays on the status line.
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Number of messages kept in the history.
+const HISTORY: usize = 100;
+
+/// Name of the status line of the game.
+const NAME: &str = "messages";
+
+struct Message {
+    text: String,
+
+    /// when the message was shown, for the history and for timing it out
+    time: DateTime<Local>,
+    shown: Instant,
+}
+
+/// A line under the board with the latest message, e.g. why a guess was
+/// not accepted, until it times out. Earlier messages are kept in a
+/// history that can be looked at again.
+#[derive(Default)]
+pub struct StatusLine {
+    messages: Vec<Message>,
+}
+
+impl StatusLine {
+    pub fn new() -> NamedView<StatusLine> {
+        StatusLine::default().with_name(NAME)
+    }
+
+    /// The latest message, if it has not timed out.
+    fn current(&self) -> Option<&str> {
+        self.messages
+            .last()
+            .filter(|message| message.shown.elapsed() < TIMEOUT)
+            .map(|message| message.text.as_str())
+    }
+}
+
+impl View for StatusLine {
+    fn draw(&self, printer: &Printer) {
+        if let Some(text) = self.current() {
+            printer.print((2, 0), text);
+        }
+    }
+
+    /// Always one line, so that the board does not move when a message
+    /// comes and goes.
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        let width = self.current().map_or(0, |text| text.chars().count() + 2);
+        Vec2::new(width, 1)
+    }
+}
+
+/// Show a message on the status line, which is cleared once it times out.
+pub fn show(s: &mut Cursive, text: impl Into<String>) {
+    let message = Message {
+        text: text.into(),
+        time: Local::now(),
+        shown: Instant::now(),
+    };
+    s.call_on_name(NAME, |view: &mut StatusLine| {
+        view.messages.push(message);
+        if view.messages.len() > HISTORY {
+            view.messages.remove(0);
+        }
+    });
+
+    // redraw once the message has timed out, even if nothing else happens
+    let sink = s.cb_sink().clone();
+    thread::spawn(move || {
+        thread::sleep(TIMEOUT);
+        // fails if the game was quit in the meantime, which is fine
+        let _ = sink.send(Box::new(|_| ()));
+    });
+}
+
+/// Show the messages so far, the latest at the bottom.
+pub fn show_history(s: &mut Cursive) {
+    let history = s
+        .call_on_name(NAME, |view: &mut StatusLine| {
+            view.messages
+                .iter()
+                .map(|message| format!("{}  {}", message.time.format("%H:%M:%S"), message.text))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+    let history = if history.is_empty() {
+        "No messages yet.".to_string()
+    } else {
+        history
+    };
+    s.add_layer(
+        Dialog::around(
+            ScrollView::new(TextView::new(history))
+                .scroll_strategy(ScrollStrategy::StickToBottom)
+                .max_height(12),
+        )
+        .title("Messages")
+        .button("Back", |s| {
+            s.pop_layer();
+        }),
+    );
+}
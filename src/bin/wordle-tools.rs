@@ -0,0 +1,304 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::path::PathBuf;
+use std::process;
+use std::rc::Rc;
+use wordle_star::dictionary::Dictionary;
+use wordle_star::difficulty::{self, Difficulty};
+use wordle_star::openers::{self, OpenerStats, SortKey};
+use wordle_star::pattern;
+use wordle_star::solver::Solver;
+use wordle_star::strategy::{RandomCandidateStrategy, SolverStrategy, Strategy};
+use wordle_star::tournament::Tournament;
+
+/// Analysis tools for wordle-star.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play the built-in strategies against the same target words and rank them
+    Tournament(TournamentArgs),
+
+    /// Score every word of the dictionary as the first guess of a game
+    ///
+    /// Every opener is scored by the candidates it leaves. The average
+    /// guesses to solve every target word are only computed for the best
+    /// openers, as many as --simulate-top asks for, since each takes about
+    /// half a minute: the other openers show "-" in that column, and
+    /// --sort guesses needs --simulate-top.
+    Openers(OpenersArgs),
+
+    /// Rate how hard each word of the dictionary is to guess, and save the
+    /// ratings next to the dictionary
+    Difficulty(DifficultyArgs),
+}
+
+/// Options shared by every command.
+#[derive(Args)]
+struct DictArgs {
+    /// File with one allowed word per line
+    #[arg(long, default_value = "data/en-dict-5letters.txt")]
+    dict: PathBuf,
+
+    /// Length of every word in the dictionary
+    #[arg(long, default_value_t = 5)]
+    word_length: usize,
+}
+
+#[derive(Args)]
+struct TournamentArgs {
+    #[command(flatten)]
+    dict: DictArgs,
+
+    /// Number of target words, picked at random from the dictionary
+    #[arg(long, default_value_t = 100)]
+    games: usize,
+
+    /// Max guesses allowed per game
+    #[arg(long, default_value_t = 6)]
+    max_attempts: usize,
+
+    /// Seed for picking the targets and the seed of each game
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Play in hard mode, where every guess must use the letters revealed
+    /// so far
+    #[arg(long)]
+    hard: bool,
+}
+
+#[derive(Args)]
+struct OpenersArgs {
+    #[command(flatten)]
+    dict: DictArgs,
+
+    /// Column to sort the report by, best opener first
+    #[arg(long, value_enum, default_value_t = OpenerSort::Expected)]
+    sort: OpenerSort,
+
+    /// Number of openers to print, all of them if not set
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Also compute the average guesses for this many openers, best by
+    /// expected remaining candidates, by solving every target word. This
+    /// takes about half a minute per opener on the default dictionary, so
+    /// it is not done for every opener
+    #[arg(long)]
+    simulate_top: Option<usize>,
+
+    /// Print comma separated values instead of a table
+    #[arg(long)]
+    csv: bool,
+}
+
+#[derive(Args)]
+struct DifficultyArgs {
+    #[command(flatten)]
+    dict: DictArgs,
+
+    /// File to save the ratings to, next to the dictionary if not set
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OpenerSort {
+    /// Expected candidates left after the opener
+    Expected,
+    /// Size of the largest group of candidates left after the opener
+    Worst,
+    /// Chance of at least one char in its correct position
+    Green,
+    /// Average guesses to solve
+    Guesses,
+    /// Alphabetical order
+    Word,
+}
+
+impl From<OpenerSort> for SortKey {
+    fn from(sort: OpenerSort) -> SortKey {
+        match sort {
+            OpenerSort::Expected => SortKey::ExpectedRemaining,
+            OpenerSort::Worst => SortKey::WorstCase,
+            OpenerSort::Green => SortKey::GreenChance,
+            OpenerSort::Guesses => SortKey::AverageGuesses,
+            OpenerSort::Word => SortKey::Word,
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Tournament(args) => tournament(args),
+        Command::Openers(args) => openers(args),
+        Command::Difficulty(args) => rate_difficulty(args),
+    }
+}
+
+/// Load the dictionary, exiting with an error message if it cannot be read.
+fn load_dict(args: &DictArgs) -> Dictionary {
+    Dictionary::load(&args.dict, args.word_length).unwrap_or_else(|err| {
+        eprintln!("could not load dictionary {}: {err}", args.dict.display());
+        process::exit(1);
+    })
+}
+
+/// Set up a solver backed by the cached pattern matrix, if possible.
+/// Exits if the words are too long for the solver.
+fn load_solver(dict: &Dictionary) -> Solver {
+    if dict.word_length > pattern::MAX_CODE_WORD_LENGTH {
+        eprintln!(
+            "the solver only supports words of up to {} letters",
+            pattern::MAX_CODE_WORD_LENGTH
+        );
+        process::exit(1);
+    }
+    let (solver, cached) = Solver::with_cached_matrix(dict.sorted_words());
+    if let Err(err) = cached {
+        eprintln!("could not cache pattern matrix: {err}");
+    }
+    solver
+}
+
+fn tournament(args: TournamentArgs) {
+    let dict = load_dict(&args.dict);
+    let solver = Rc::new(load_solver(&dict));
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let words = dict.sorted_words();
+    let targets: Vec<String> = words
+        .choose_multiple(&mut rng, args.games)
+        .cloned()
+        .collect();
+
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![
+        Box::new(SolverStrategy::new(solver.clone())),
+        Box::new(RandomCandidateStrategy::new(solver)),
+    ];
+    let mut tournament = Tournament::new(dict, targets, args.max_attempts, args.seed);
+    tournament.set_hard_mode(args.hard);
+    let standings = tournament.run(&mut strategies);
+
+    println!(
+        "{:<4} {:<20} {:>6} {:>6} {:>8} {:>10} {:>8}  distribution",
+        "rank", "strategy", "played", "wins", "win %", "avg tries", "invalid"
+    );
+    for (rank, standing) in standings.iter().enumerate() {
+        let average = standing
+            .average_guesses()
+            .map_or("-".to_string(), |avg| format!("{avg:.3}"));
+        let distribution: Vec<String> = standing
+            .distribution
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        println!(
+            "{:<4} {:<20} {:>6} {:>6} {:>8.1} {:>10} {:>8}  {}",
+            rank + 1,
+            standing.name,
+            standing.played,
+            standing.wins,
+            standing.win_rate() * 100.0,
+            average,
+            standing.invalid_guesses,
+            distribution.join(" ")
+        );
+    }
+}
+
+fn openers(args: OpenersArgs) {
+    let dict = load_dict(&args.dict);
+    let solver = load_solver(&dict);
+
+    let simulate_top = match (args.simulate_top, args.sort) {
+        (Some(simulate_top), _) => simulate_top,
+        (None, OpenerSort::Guesses) => {
+            eprintln!("--sort guesses needs --simulate-top");
+            process::exit(1);
+        }
+        (None, _) => 0,
+    };
+    let mut stats = openers::rank_openers(&solver, simulate_top);
+    if let OpenerSort::Guesses = args.sort {
+        // only the simulated openers can be ranked by their guesses
+        stats.retain(|opener| opener.average_guesses.is_some());
+    }
+    openers::sort_openers(&mut stats, args.sort.into());
+    let limit = args.limit.unwrap_or(stats.len());
+    let stats = &stats[..limit.min(stats.len())];
+
+    if args.csv {
+        print_openers_csv(stats);
+    } else {
+        print_openers_table(stats);
+    }
+}
+
+fn print_openers_csv(stats: &[OpenerStats]) {
+    println!("word,expected_remaining,worst_case,green_chance,average_guesses");
+    for opener in stats {
+        let average = opener
+            .average_guesses
+            .map_or(String::new(), |avg| format!("{avg:.4}"));
+        println!(
+            "{},{:.4},{},{:.4},{}",
+            opener.word, opener.expected_remaining, opener.worst_case, opener.green_chance, average
+        );
+    }
+}
+
+fn print_openers_table(stats: &[OpenerStats]) {
+    println!(
+        "{:<4} {:<10} {:>10} {:>6} {:>8} {:>10}",
+        "rank", "opener", "expected", "worst", "green %", "avg tries"
+    );
+    for (rank, opener) in stats.iter().enumerate() {
+        let average = opener
+            .average_guesses
+            .map_or("-".to_string(), |avg| format!("{avg:.3}"));
+        println!(
+            "{:<4} {:<10} {:>10.2} {:>6} {:>8.1} {:>10}",
+            rank + 1,
+            opener.word,
+            opener.expected_remaining,
+            opener.worst_case,
+            opener.green_chance * 100.0,
+            average
+        );
+    }
+}
+
+fn rate_difficulty(args: DifficultyArgs) {
+    let mut dict = load_dict(&args.dict);
+    let solver = load_solver(&dict);
+
+    let ratings = difficulty::rate_words(&solver);
+    for (word, rating) in solver.words().iter().zip(ratings.iter()) {
+        dict.set_difficulty(word, *rating);
+    }
+
+    let output = args
+        .output
+        .unwrap_or_else(|| difficulty::metadata_path(&args.dict.dict));
+    if let Err(err) = dict.save_difficulty(&output) {
+        eprintln!("could not save ratings to {}: {err}", output.display());
+        process::exit(1);
+    }
+
+    println!("{:<8} {:>6}", "level", "words");
+    for level in Difficulty::ALL {
+        let words = ratings.iter().filter(|r| r.level() == level).count();
+        println!("{:<8} {:>6}", level.to_string(), words);
+    }
+    println!("saved ratings to {}", output.display());
+}
@@ -0,0 +1,27 @@
+use crate::guess::GuessResult;
+use crate::keyboard_view::KeyboardView;
+use crate::knowledge::Knowledge;
+
+/// Read only view of a wordle board: the guesses submitted so far and the
+/// aggregate state of the keyboard. Front ends render anything that
+/// implements it, be it a game or an assistant for an external puzzle.
+pub trait Board {
+    /// Results of each guess in order of submission.
+    fn guess_results(&self) -> &[GuessResult];
+
+    /// State of the keyboard, given the guesses so far.
+    fn keyboard_view(&self) -> &KeyboardView;
+
+    /// Length of every word on the board.
+    fn word_length(&self) -> usize;
+
+    /// Number of guesses allowed, if there is a limit.
+    fn max_guesses(&self) -> Option<usize> {
+        None
+    }
+
+    /// What the guesses so far reveal about the target, slot by slot.
+    fn knowledge(&self) -> Knowledge {
+        Knowledge::from_guess_results(self.word_length(), self.guess_results())
+    }
+}
@@ -0,0 +1,227 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Preferences that may be set in the config file. Anything left out falls
+/// back to the defaults of the front end.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Settings {
+    /// "play" or "assist"
+    pub mode: Option<String>,
+    pub max_attempts: Option<usize>,
+    pub word_length: Option<usize>,
+    pub hard: Option<bool>,
+    pub difficulty: Option<String>,
+
+    pub dict: Option<PathBuf>,
+    pub answers: Option<PathBuf>,
+
+    /// name of the keyboard layout, and the file with custom layouts
+    pub layout: Option<String>,
+    pub layout_file: Option<PathBuf>,
+
+    /// cursive theme file with the colours of the interface
+    pub theme: Option<PathBuf>,
+
+    /// colours of the tiles, e.g. "colour-blind"
+    pub palette: Option<String>,
+
+    /// no animations of the tiles
+    pub reduced_motion: Option<bool>,
+
+    /// "tui", "accessible" to read out the game in lines of text, or
+    /// "plain" for lines on stdin and stdout
+    pub interface: Option<String>,
+
+    /// seconds each game may last, or whether to time each game and the
+    /// series of games
+    pub countdown: Option<u64>,
+    pub stopwatch: Option<bool>,
+    pub series: Option<usize>,
+    pub share_style: Option<String>,
+
+    /// key of each action, by name of the action, e.g. give-up = "F3"
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+impl Settings {
+    /// These settings, with the ones set in `other` taking precedence.
+    pub fn merge(self, other: Settings) -> Settings {
+        let mut keys = self.keys;
+        keys.extend(other.keys);
+        Settings {
+            mode: other.mode.or(self.mode),
+            max_attempts: other.max_attempts.or(self.max_attempts),
+            word_length: other.word_length.or(self.word_length),
+            hard: other.hard.or(self.hard),
+            difficulty: other.difficulty.or(self.difficulty),
+            dict: other.dict.or(self.dict),
+            answers: other.answers.or(self.answers),
+            layout: other.layout.or(self.layout),
+            layout_file: other.layout_file.or(self.layout_file),
+            theme: other.theme.or(self.theme),
+            palette: other.palette.or(self.palette),
+            reduced_motion: other.reduced_motion.or(self.reduced_motion),
+            interface: other.interface.or(self.interface),
+            countdown: other.countdown.or(self.countdown),
+            stopwatch: other.stopwatch.or(self.stopwatch),
+            series: other.series.or(self.series),
+            share_style: other.share_style.or(self.share_style),
+            keys,
+        }
+    }
+}
+
+/// Contents of the config file: default settings at the top level, and
+/// named profiles that override them, e.g.
+///
+/// ```toml
+/// max-attempts = 6
+/// layout = "dvorak"
+///
+/// [keys]
+/// statistics = "F5"
+///
+/// [profile.hard]
+/// hard = true
+/// max-attempts = 5
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub defaults: Settings,
+    pub profiles: BTreeMap<String, Settings>,
+}
+
+/// Problems with the config file.
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    /// the file is not valid TOML, or has unknown settings
+    Parse(String),
+
+    /// the profile asked for is not in the file
+    UnknownProfile(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Parse(message) => write!(f, "invalid config file: {message}"),
+            ConfigError::UnknownProfile(name) => write!(f, "no profile named {name}"),
+        }
+    }
+}
+
+impl Config {
+    /// Parse the contents of a config file.
+    pub fn parse(contents: &str) -> Result<Config, ConfigError> {
+        let parse_error = |err: toml::de::Error| ConfigError::Parse(err.to_string());
+        let mut table: toml::Table = toml::from_str(contents).map_err(parse_error)?;
+        let profiles = match table.remove("profile") {
+            Some(profiles) => profiles.try_into().map_err(parse_error)?,
+            None => BTreeMap::new(),
+        };
+        let defaults = toml::Value::Table(table).try_into().map_err(parse_error)?;
+        Ok(Config { defaults, profiles })
+    }
+
+    /// Config file in the user's config directory, e.g.
+    /// ~/.config/wordle-star/config.toml.
+    /// Returns None if the platform has no config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("wordle-star").join("config.toml"))
+    }
+
+    /// Settings of a profile on top of the defaults, or only the defaults.
+    pub fn settings(&self, profile: Option<&str>) -> Result<Settings, ConfigError> {
+        match profile {
+            None => Ok(self.defaults.clone()),
+            Some(name) => match self.profiles.get(name) {
+                Some(settings) => Ok(self.defaults.clone().merge(settings.clone())),
+                None => Err(ConfigError::UnknownProfile(name.to_string())),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        max-attempts = 6
+        layout = "dvorak"
+        dict = "words.txt"
+        palette = "monochrome"
+
+        [keys]
+        statistics = "F5"
+        give-up = "F3"
+
+        [profile.hard]
+        hard = true
+        max-attempts = 5
+
+        [profile.work]
+        mode = "assist"
+        reduced-motion = true
+        keys = { give-up = "F9" }
+    "#;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(CONFIG).unwrap();
+        assert_eq!(Some(6), config.defaults.max_attempts);
+        assert_eq!(Some("dvorak".to_string()), config.defaults.layout);
+        assert_eq!(Some(PathBuf::from("words.txt")), config.defaults.dict);
+        assert_eq!(None, config.defaults.hard);
+        assert_eq!("F5", config.defaults.keys["statistics"]);
+        assert_eq!(
+            vec!["hard", "work"],
+            config.profiles.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(Config::default(), Config::parse("").unwrap());
+    }
+
+    #[test]
+    fn test_profile_overrides_defaults() {
+        let config = Config::parse(CONFIG).unwrap();
+        assert_eq!(config.defaults, config.settings(None).unwrap());
+
+        let hard = config.settings(Some("hard")).unwrap();
+        assert_eq!(Some(true), hard.hard);
+        assert_eq!(Some(5), hard.max_attempts);
+        assert_eq!(Some("dvorak".to_string()), hard.layout);
+        assert_eq!(Some("monochrome".to_string()), hard.palette);
+
+        let work = config.settings(Some("work")).unwrap();
+        assert_eq!(Some("assist".to_string()), work.mode);
+        assert_eq!(Some(6), work.max_attempts);
+        assert_eq!(Some(true), work.reduced_motion);
+        assert_eq!("F5", work.keys["statistics"]);
+        assert_eq!("F9", work.keys["give-up"]);
+
+        assert_eq!(
+            Err(ConfigError::UnknownProfile("home".to_string())),
+            config.settings(Some("home"))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Config::parse("max-atempts = 6"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            Config::parse("[profile.hard]\nhard = \"yes\""),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            Config::parse("mode ="),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+}
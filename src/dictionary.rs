@@ -1,12 +1,22 @@
+use crate::difficulty::Difficulty;
+use crate::difficulty::DifficultyRating;
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
 
 /// A dictionary of fixed length words. All words in the dictionary
 /// have the same length drawn from the same alphabet.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Dictionary {
     pub wordset: HashSet<String>,
     pub word_length: usize,
+
+    /// difficulty of the words as targets, if it has been rated
+    difficulty: HashMap<String, DifficultyRating>,
 }
 
 impl Dictionary {
@@ -17,7 +27,28 @@ impl Dictionary {
         Dictionary {
             wordset: HashSet::new(),
             word_length: word_length,
+            difficulty: HashMap::new(),
+        }
+    }
+
+    /// Load a dictionary from a file with one word per line.
+    /// Blank lines are skipped, and every other word must have the provided length.
+    pub fn load<P: AsRef<Path>>(path: P, word_length: usize) -> io::Result<Dictionary> {
+        let mut dict = Dictionary::new(word_length);
+        for line in fs::read_to_string(path)?.lines() {
+            let word = line.trim();
+            if word.is_empty() {
+                continue;
+            }
+            if word.chars().count() != word_length {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{word} does not have {word_length} letters"),
+                ));
+            }
+            dict.add_word_str(word);
         }
+        Ok(dict)
     }
 
     pub fn add_word(&mut self, word: &String) {
@@ -48,6 +79,92 @@ impl Dictionary {
     pub fn len(&self) -> usize {
         self.wordset.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.wordset.is_empty()
+    }
+
+    /// Record how hard a word is to guess.
+    /// panics if the word is not in the dictionary.
+    pub fn set_difficulty(&mut self, word: &str, rating: DifficultyRating) {
+        if !self.wordset.contains(word) {
+            panic!("{word} is not in the dictionary");
+        }
+        self.difficulty.insert(word.to_string(), rating);
+    }
+
+    /// How hard a word is to guess, if it has been rated.
+    pub fn difficulty(&self, word: &str) -> Option<DifficultyRating> {
+        self.difficulty.get(word).copied()
+    }
+
+    /// A random word of the provided difficulty.
+    /// Returns None if no word has been rated at that difficulty.
+    pub fn random_word_with_difficulty(&self, level: Difficulty) -> Option<String> {
+        let mut words: Vec<&String> = self
+            .difficulty
+            .iter()
+            .filter(|(_, rating)| rating.level() == level)
+            .map(|(word, _)| word)
+            .collect();
+        // sort so that the choice only depends on the rng
+        words.sort();
+        words.choose(&mut thread_rng()).map(|word| word.to_string())
+    }
+
+    /// Load difficulty ratings from a file with one word per line, followed
+    /// by its solver guesses, human guesses and trap group size.
+    /// Lines starting with # are skipped, and so are words not in the dictionary.
+    pub fn load_difficulty<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        for line in fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid difficulty rating: {line}"),
+                )
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 4 {
+                return Err(invalid());
+            }
+            let number = |field: &str| field.parse::<usize>().map_err(|_| invalid());
+            let rating = DifficultyRating {
+                solver_guesses: number(fields[1])?,
+                human_guesses: number(fields[2])?,
+                trap_group: number(fields[3])?,
+            };
+            if self.wordset.contains(fields[0]) {
+                self.difficulty.insert(fields[0].to_string(), rating);
+            }
+        }
+        Ok(())
+    }
+
+    /// Save the difficulty ratings in the format read by `load_difficulty`.
+    pub fn save_difficulty<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut words: Vec<&String> = self.difficulty.keys().collect();
+        words.sort();
+        let mut contents = String::from("# word solver_guesses human_guesses trap_group\n");
+        for word in words {
+            let rating = self.difficulty[word];
+            contents.push_str(&format!(
+                "{word} {} {} {}\n",
+                rating.solver_guesses, rating.human_guesses, rating.trap_group
+            ));
+        }
+        fs::write(path, contents)
+    }
+
+    /// All words of the dictionary in sorted order.
+    pub fn sorted_words(&self) -> Vec<String> {
+        let mut words = Vec::from_iter(self.wordset.iter().cloned());
+        words.sort();
+        words
+    }
 }
 
 #[cfg(test)]
@@ -102,4 +219,106 @@ mod tests {
         let r = dict.random_word();
         assert!(r == "abcd" || r == "bcda");
     }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join(format!("wordle-star-dict-{}", std::process::id()));
+        fs::write(&path, "abc\n\n bcd \n").unwrap();
+        let dict = Dictionary::load(&path, 3).unwrap();
+        assert_eq!(
+            vec!["abc".to_string(), "bcd".to_string()],
+            dict.sorted_words()
+        );
+
+        let err = Dictionary::load(&path, 4).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        fs::remove_file(&path).unwrap();
+
+        assert!(Dictionary::load(&path, 3).is_err());
+    }
+
+    #[test]
+    fn test_difficulty() {
+        let mut dict = Dictionary::new(2);
+        dict.add_word_str("ab");
+        dict.add_word_str("bc");
+        let easy = DifficultyRating {
+            solver_guesses: 2,
+            human_guesses: 2,
+            trap_group: 1,
+        };
+        let expert = DifficultyRating {
+            solver_guesses: 6,
+            human_guesses: 8,
+            trap_group: 4,
+        };
+        assert_eq!(None, dict.difficulty("ab"));
+        assert_eq!(None, dict.random_word_with_difficulty(Difficulty::Easy));
+
+        dict.set_difficulty("ab", easy);
+        dict.set_difficulty("bc", expert);
+        assert_eq!(Some(easy), dict.difficulty("ab"));
+        assert_eq!(
+            Some("ab".to_string()),
+            dict.random_word_with_difficulty(Difficulty::Easy)
+        );
+        assert_eq!(
+            Some("bc".to_string()),
+            dict.random_word_with_difficulty(Difficulty::Expert)
+        );
+        assert_eq!(None, dict.random_word_with_difficulty(Difficulty::Medium));
+    }
+
+    #[test]
+    #[should_panic(expected = "zz is not in the dictionary")]
+    fn test_difficulty_unknown_word() {
+        let mut dict = Dictionary::new(2);
+        dict.set_difficulty(
+            "zz",
+            DifficultyRating {
+                solver_guesses: 1,
+                human_guesses: 1,
+                trap_group: 1,
+            },
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_difficulty() {
+        let path =
+            std::env::temp_dir().join(format!("wordle-star-difficulty-{}", std::process::id()));
+        let mut dict = Dictionary::new(2);
+        dict.add_word_str("ab");
+        dict.add_word_str("bc");
+        let rating = DifficultyRating {
+            solver_guesses: 3,
+            human_guesses: 4,
+            trap_group: 2,
+        };
+        dict.set_difficulty("bc", rating);
+        dict.save_difficulty(&path).unwrap();
+
+        let mut loaded = dict.clone();
+        loaded.difficulty.clear();
+        loaded.load_difficulty(&path).unwrap();
+        assert_eq!(Some(rating), loaded.difficulty("bc"));
+        assert_eq!(None, loaded.difficulty("ab"));
+
+        fs::write(&path, "ab 3 x 1\n").unwrap();
+        let err = loaded.load_difficulty(&path).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sorted_words() {
+        let mut dict = Dictionary::new(2);
+        dict.add_word_str("bc");
+        dict.add_word_str("ab");
+
+        assert_eq!(
+            vec!["ab".to_string(), "bc".to_string()],
+            dict.sorted_words()
+        );
+    }
 }
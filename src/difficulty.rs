@@ -0,0 +1,203 @@
+use crate::pattern;
+use crate::solver::Solver;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// How hard a target word is to guess, from easiest to hardest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// What makes a target word hard, measured against the whole dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DifficultyRating {
+    /// guesses the built-in solver needs to find the word
+    pub solver_guesses: usize,
+
+    /// guesses a player who always plays the candidate with the most common
+    /// letters needs to find the word
+    pub human_guesses: usize,
+
+    /// number of words, this one included, that share every letter but one
+    /// with this word, like the _IGHT words
+    pub trap_group: usize,
+}
+
+impl DifficultyRating {
+    /// Expected guesses to find the word, with a penalty for large trap
+    /// groups, where a player may have to try the words one by one.
+    pub fn score(&self) -> f64 {
+        let guesses = (self.solver_guesses + self.human_guesses) as f64 / 2.0;
+        guesses + (self.trap_group.max(1) as f64).log2() / 2.0
+    }
+
+    pub fn level(&self) -> Difficulty {
+        let score = self.score();
+        if score < 4.5 {
+            Difficulty::Easy
+        } else if score < 5.25 {
+            Difficulty::Medium
+        } else if score < 6.0 {
+            Difficulty::Hard
+        } else {
+            Difficulty::Expert
+        }
+    }
+}
+
+/// File with the difficulty ratings of a dictionary, next to the dictionary.
+pub fn metadata_path(dict_path: &Path) -> PathBuf {
+    dict_path.with_extension("difficulty")
+}
+
+/// Size of the largest trap group of each word, in the same order as the words.
+/// A trap group is a set of words that only differ at one position.
+pub fn trap_groups(words: &[String]) -> Vec<usize> {
+    let chars: Vec<Vec<char>> = words.iter().map(|w| w.chars().collect()).collect();
+    let word_length = chars.first().map_or(0, |c| c.len());
+
+    let mut groups = vec![1; words.len()];
+    for pos in 0..word_length {
+        // words keyed by their letters, except the one at pos
+        let mut group_sizes: HashMap<Vec<char>, usize> = HashMap::new();
+        let masked = |word: &[char]| -> Vec<char> {
+            let mut masked = word.to_vec();
+            masked[pos] = '_';
+            masked
+        };
+        for word in &chars {
+            *group_sizes.entry(masked(word)).or_insert(0) += 1;
+        }
+        for (group, word) in groups.iter_mut().zip(chars.iter()) {
+            *group = (*group).max(group_sizes[&masked(word)]);
+        }
+    }
+    groups
+}
+
+/// Guesses needed to find the target by always playing the candidate with
+/// the most common letters, like a player who does not plan ahead.
+pub fn human_guesses(solver: &Solver, target: usize) -> usize {
+    let correct = pattern::correct_code(solver.words()[target].chars().count());
+    let mut candidates = solver.all_candidates();
+    let mut guesses = 0;
+    // the guess is always a candidate, and it is dropped unless it is the
+    // target, so this ends within one guess per word
+    while let Some(guess) = solver.most_frequent_letters(&candidates) {
+        guesses += 1;
+        let code = solver.pattern(guess, target);
+        if code == correct {
+            break;
+        }
+        candidates.retain(|&candidate| solver.pattern(guess, candidate) == code);
+    }
+    guesses
+}
+
+/// Rate every word of the solver as a target, in the same order as the words.
+pub fn rate_words(solver: &Solver) -> Vec<DifficultyRating> {
+    let all_words = solver.all_candidates();
+    let mut solver_guesses = vec![0; all_words.len()];
+    for (target, guesses) in solver.guesses_per_target(None, &all_words) {
+        solver_guesses[target] = guesses;
+    }
+    let traps = trap_groups(solver.words());
+
+    all_words
+        .par_iter()
+        .map(|&target| DifficultyRating {
+            solver_guesses: solver_guesses[target],
+            human_guesses: human_guesses(solver, target),
+            trap_group: traps[target],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_trap_groups() {
+        let words = words(&[
+            "fight", "light", "might", "right", "sight", "lamps", "lamas",
+        ]);
+        assert_eq!(vec![5, 5, 5, 5, 5, 2, 2], trap_groups(&words));
+        assert_eq!(vec![1], trap_groups(&["solo".to_string()]));
+    }
+
+    #[test]
+    fn test_human_guesses() {
+        let solver = Solver::new(words(&["bat", "cat", "dog"]));
+        // "bat" and "cat" share the most common letters, "bat" wins the tie
+        assert_eq!(1, human_guesses(&solver, 0));
+        assert_eq!(2, human_guesses(&solver, 1));
+        assert_eq!(2, human_guesses(&solver, 2));
+    }
+
+    #[test]
+    fn test_rating_levels() {
+        let rating = |solver_guesses, human_guesses, trap_group| DifficultyRating {
+            solver_guesses,
+            human_guesses,
+            trap_group,
+        };
+        assert_eq!(Difficulty::Easy, rating(3, 3, 1).level());
+        assert_eq!(Difficulty::Medium, rating(4, 5, 2).level());
+        assert_eq!(Difficulty::Hard, rating(4, 5, 4).level());
+        assert_eq!(Difficulty::Expert, rating(5, 7, 8).level());
+        assert!(rating(3, 3, 8).score() > rating(3, 3, 1).score());
+    }
+
+    #[test]
+    fn test_rate_words() {
+        let solver = Solver::new(words(&["fight", "light", "might", "right", "crane"]));
+        let ratings = rate_words(&solver);
+        assert_eq!(5, ratings.len());
+        assert_eq!(4, ratings[0].trap_group);
+        assert_eq!(1, ratings[4].trap_group);
+        assert!(ratings
+            .iter()
+            .all(|r| r.solver_guesses >= 1 && r.human_guesses >= 1));
+    }
+
+    #[test]
+    fn test_metadata_path() {
+        assert_eq!(
+            PathBuf::from("data/en.difficulty"),
+            metadata_path(Path::new("data/en.txt"))
+        );
+    }
+}
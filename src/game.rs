@@ -1,17 +1,103 @@
+use crate::board::Board;
 use crate::dictionary::Dictionary;
-use crate::guess::CharState;
+use crate::difficulty::DifficultyRating;
+use crate::guess;
 use crate::guess::GuessResult;
 use crate::keyboard_view::KeyboardView;
-use std::cmp;
-use std::collections::HashMap;
-use std::collections::HashSet;
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+use std::time::Instant;
 
 /// Enum representing current state of the game.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum GameState {
     Playing,
     Win,
     Lose,
+
+    /// the time limit passed before the target was found
+    OutOfTime,
+}
+
+/// Reasons a guess is not accepted.
+#[derive(Debug, PartialEq)]
+pub enum GuessError {
+    /// the word is not in the dictionary
+    NotInDictionary,
+
+    /// hard mode: a letter revealed at a position, 0 based, is not kept there
+    MissingLetter { position: usize, letter: char },
+
+    /// hard mode: a revealed letter is not used as often as it is known to appear
+    MissingHint(char),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::NotInDictionary => write!(f, "not a valid word"),
+            GuessError::MissingLetter { position, letter } => {
+                write!(f, "letter {} must be {letter}", position + 1)
+            }
+            GuessError::MissingHint(letter) => write!(f, "guess must contain {letter}"),
+        }
+    }
+}
+
+/// Reasons a submitted guess is not counted.
+#[derive(Debug, PartialEq)]
+pub enum SubmitError {
+    /// the game was already over
+    GameOver,
+
+    /// the time limit passed, which ended the game just now
+    OutOfTime,
+
+    /// the guess does not have as many letters as the target
+    WrongLength { letters: usize, expected: usize },
+
+    /// the guess may not be played, see `check_guess`
+    Invalid(GuessError),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::GameOver => write!(f, "the game is over"),
+            SubmitError::OutOfTime => write!(f, "out of time"),
+            SubmitError::WrongLength { expected, .. } => {
+                write!(f, "guesses must have {expected} letters")
+            }
+            SubmitError::Invalid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// What a player may know about a game in progress: the rules, and the
+/// results of the guesses so far. It never reveals the target word.
+#[derive(Debug)]
+pub struct GameView<'a> {
+    /// results of each guess in order of submission
+    pub guess_results: &'a [GuessResult],
+
+    /// max guesses allowed
+    pub max_guesses: usize,
+
+    /// length of every word that may be guessed
+    pub word_length: usize,
+
+    /// whether every guess must use the letters revealed so far, see
+    /// `Game::check_guess`
+    pub hard_mode: bool,
+}
+
+impl<'a> GameView<'a> {
+    /// Number of guesses that may still be submitted.
+    pub fn remaining_guesses(&self) -> usize {
+        self.max_guesses.saturating_sub(self.guess_results.len())
+    }
 }
 
 /// This struct encapsulates all properties of a wordle game.
@@ -22,9 +108,6 @@ pub struct Game {
     /// word that has to be guessed, must be in the dictionary
     pub target_word: String,
 
-    /// helper map to store index of each character in the target word
-    target_char_indexes: HashMap<char, HashSet<usize>>,
-
     /// max guesses allowed
     max_guesses: usize,
 
@@ -37,6 +120,16 @@ pub struct Game {
 
     /// state of the game, in progress or finished?
     pub state: GameState,
+
+    /// whether every guess must use the letters revealed so far
+    hard_mode: bool,
+
+    /// time the game may last, if it is limited
+    time_limit: Option<Duration>,
+
+    /// when the game was created, and when it was won or lost
+    started: Instant,
+    finished: Option<Instant>,
 }
 
 impl Game {
@@ -47,22 +140,118 @@ impl Game {
         if !Game::is_word_allowed_in_dict(&dict, &target_word) {
             panic!("target word not present in dictionary");
         }
-        let positions_map = Game::compute_char_positions_map(&target_word);
         return Game {
             dict: dict,
             target_word: target_word.to_string(),
-            target_char_indexes: positions_map,
             keyboard_view: KeyboardView::new(),
             guess_results: vec![],
             state: GameState::Playing,
             max_guesses: max_guesses,
+            hard_mode: false,
+            time_limit: None,
+            started: Instant::now(),
+            finished: None,
+        };
+    }
+
+    /// Start over with a new target word from the same dictionary, clearing
+    /// the guesses and the keyboard.
+    /// panics if the target word is not in the dictionary.
+    pub fn new_round(&mut self, target_word: &str) {
+        if !Game::is_word_allowed_in_dict(&self.dict, target_word) {
+            panic!("target word not present in dictionary");
+        }
+        self.target_word = target_word.to_string();
+        self.keyboard_view = KeyboardView::new();
+        self.guess_results.clear();
+        self.state = GameState::Playing;
+        self.started = Instant::now();
+        self.finished = None;
+    }
+
+    /// Stop guessing, which loses the game.
+    /// panics if the game is already over.
+    pub fn give_up(&mut self) {
+        if self.state != GameState::Playing {
+            panic!("game is already over");
+        }
+        self.state = GameState::Lose;
+        self.finished = Some(Instant::now());
+    }
+
+    /// In hard mode, letters revealed in the right position must be kept
+    /// there, and letters revealed in the wrong position must be used again.
+    pub fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Limit the time each round may last, counting from its start. The game
+    /// is only out of time once `check_time` notices, or on the next guess.
+    pub fn set_time_limit(&mut self, time_limit: Option<Duration>) {
+        self.time_limit = time_limit;
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    /// Time left before the limit, if there is one.
+    pub fn time_left(&self) -> Option<Duration> {
+        self.time_limit
+            .map(|limit| limit.saturating_sub(self.duration()))
+    }
+
+    /// End the game if it is being played and its time limit has passed.
+    /// Returns whether it ran out of time just now.
+    pub fn check_time(&mut self) -> bool {
+        let limit = match self.time_limit {
+            Some(limit) if self.state == GameState::Playing => limit,
+            _ => return false,
         };
+        if self.duration() < limit {
+            return false;
+        }
+        self.state = GameState::OutOfTime;
+        self.finished = Some(self.started + limit);
+        true
+    }
+
+    /// Check that a word may be submitted as the next guess: it must be in
+    /// the dictionary, and follow the hints so far in hard mode.
+    pub fn check_guess(&self, word: &str) -> Result<(), GuessError> {
+        if !self.is_word_allowed(word) {
+            return Err(GuessError::NotInDictionary);
+        }
+        if !self.hard_mode {
+            return Ok(());
+        }
+
+        let knowledge = self.knowledge();
+        let chars: Vec<char> = word.chars().collect();
+        for (position, &ch) in chars.iter().enumerate() {
+            if let Some(letter) = knowledge.fixed(position) {
+                if ch != letter {
+                    return Err(GuessError::MissingLetter { position, letter });
+                }
+            }
+        }
+        for (letter, min_count, _) in knowledge.letter_counts() {
+            if chars.iter().filter(|&&ch| ch == letter).count() < min_count {
+                return Err(GuessError::MissingHint(letter));
+            }
+        }
+        Ok(())
     }
 
     /// Submit a guess to the game.
     /// panics if max guesses have already been submitted.
     /// Returns an `Option` of `GuessResult`.
-    ///  -> None if the word is not allowed per the dictionary.
+    ///  -> None if the word is not allowed, see `check_guess`, or if it
+    ///     comes after the time limit, which ends the game.
     ///  -> Some(GuessResult) contains the result of submitting a guess.
     /// The internal state of the game is updated based on the submission.
     /// Game may be marked as won or lost accordingly. Other internal states
@@ -71,17 +260,18 @@ impl Game {
         if !self.allow_more_guesses() {
             panic!("no more guesses allowed")
         }
+        if self.check_time() {
+            return None;
+        }
 
         // TODO: normalize for casing
-        if !self.is_word_allowed(&word) {
+        if self.check_guess(word).is_err() {
             return None;
         }
 
-        let guess_result = self.compute_guess_result(&word);
+        let guess_result = self.compute_guess_result(word);
 
-        for char_guess in (&guess_result.char_guesses).into_iter() {
-            self.keyboard_view.record_guess(&char_guess);
-        }
+        self.keyboard_view.record_guess_result(&guess_result);
 
         // append to internal guess results for later use
         self.guess_results.push(guess_result.clone());
@@ -91,10 +281,70 @@ impl Game {
         } else if !self.allow_more_guesses() {
             self.state = GameState::Lose;
         }
+        if self.state != GameState::Playing {
+            self.finished = Some(Instant::now());
+        }
 
         Some(guess_result)
     }
 
+    /// Submit a guess typed by the player, and say why it does not count
+    /// if it is rejected. Unlike `guess_word`, this never panics: a guess
+    /// made once the game is over is an error.
+    pub fn submit(&mut self, word: &str) -> Result<GuessResult, SubmitError> {
+        if self.state != GameState::Playing {
+            return Err(SubmitError::GameOver);
+        }
+        if self.check_time() {
+            return Err(SubmitError::OutOfTime);
+        }
+        let letters = word.chars().count();
+        if letters != self.word_length() {
+            return Err(SubmitError::WrongLength {
+                letters,
+                expected: self.word_length(),
+            });
+        }
+        self.check_guess(word).map_err(SubmitError::Invalid)?;
+        // None only if the time limit passed since it was checked
+        self.guess_word(word).ok_or(SubmitError::OutOfTime)
+    }
+
+    /// Time spent on the game so far, or until it was won or lost.
+    pub fn duration(&self) -> Duration {
+        self.finished.unwrap_or_else(Instant::now) - self.started
+    }
+
+    /// Dictionary of the words that may be guessed.
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dict
+    }
+
+    /// Max guesses allowed in this game.
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    /// Length of every word that may be guessed.
+    pub fn word_length(&self) -> usize {
+        self.dict.word_length
+    }
+
+    /// View of the game for a player, which does not reveal the target word.
+    pub fn view(&self) -> GameView<'_> {
+        GameView {
+            guess_results: &self.guess_results,
+            max_guesses: self.max_guesses,
+            word_length: self.word_length(),
+            hard_mode: self.hard_mode,
+        }
+    }
+
+    /// How hard the target word is to guess, if the dictionary has it rated.
+    pub fn target_difficulty(&self) -> Option<DifficultyRating> {
+        self.dict.difficulty(&self.target_word)
+    }
+
     /// Check if the provided word is allowed per the dictionary.
     fn is_word_allowed_in_dict(dict: &Dictionary, word: &str) -> bool {
         dict.contains(&word.to_string())
@@ -112,69 +362,33 @@ impl Game {
 
     /// Internal helper method that computes the guess result for the provided word.
     /// Assumes that the word is in the dictionary.
-    fn compute_guess_result(&mut self, word: &str) -> GuessResult {
-        let mut char_guesses = vec![];
-
-        for ch in word.chars() {
-            char_guesses.push((ch, CharState::NotFound));
-        }
+    fn compute_guess_result(&self, word: &str) -> GuessResult {
+        guess::compute_guess_result(word, &self.target_word)
+    }
+}
 
-        let guess_map = Game::compute_char_positions_map(&word);
-
-        for (&ch, target_positions) in &self.target_char_indexes {
-            match guess_map.get(&ch) {
-                None => (),
-                Some(guess_positions) => {
-                    let intersection = guess_positions.intersection(&target_positions);
-                    let mut intersection_len = 0;
-                    for correct_position in intersection {
-                        char_guesses[*correct_position] = (ch, CharState::CorrectPosition);
-                        intersection_len += 1;
-                    }
-
-                    // how many occurrences of ch in target but not in the intersection?
-                    let extra_count = target_positions.len() - intersection_len;
-                    // positions for this character in guess but not in target
-                    let diff = guess_positions.difference(target_positions);
-                    // there are extra positions for this character that were not counted in intersection.
-                    // there are also some occurences of this character in the guess.
-                    // pick extra_count positions from the diff, pick the smaller ones.
-                    let mut sorted_diff = diff.map(|x| *x).collect::<Vec<usize>>();
-                    sorted_diff.sort();
-
-                    if extra_count > 0 {
-                        let trimmed_length = cmp::min(extra_count, sorted_diff.len());
-                        for incorrect_position in sorted_diff[0..trimmed_length].into_iter() {
-                            char_guesses[*incorrect_position] = (ch, CharState::IncorrectPosition);
-                        }
-                    }
-                }
-            }
-        }
+impl Board for Game {
+    fn guess_results(&self) -> &[GuessResult] {
+        &self.guess_results
+    }
 
-        GuessResult::new(char_guesses)
+    fn keyboard_view(&self) -> &KeyboardView {
+        &self.keyboard_view
     }
 
-    /// Compute a map of indexes of each character in the word provided.
-    fn compute_char_positions_map(word: &str) -> HashMap<char, HashSet<usize>> {
-        let mut map = HashMap::new();
-        for (index, ch) in word.chars().enumerate() {
-            if let None = map.get(&ch) {
-                // first occurrence of a character
-                map.insert(ch, HashSet::new());
-            }
+    fn word_length(&self) -> usize {
+        self.dict.word_length
+    }
 
-            // must be found, safe to unwrap
-            let positions = map.get_mut(&ch).unwrap();
-            positions.insert(index);
-        }
-        map
+    fn max_guesses(&self) -> Option<usize> {
+        Some(self.max_guesses)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::guess::CharState;
 
     #[test]
     fn test_game_setup() {
@@ -233,6 +447,197 @@ mod tests {
         game.guess_word("dog");
     }
 
+    #[test]
+    fn test_game_view() {
+        let mut game = Game::new(basic_dict(), "mat", 6);
+        game.guess_word("sat");
+
+        let view = game.view();
+        assert_eq!(1, view.guess_results.len());
+        assert_eq!(6, view.max_guesses);
+        assert_eq!(5, view.remaining_guesses());
+        assert_eq!(3, view.word_length);
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        let mut dict = Dictionary::new(5);
+        for word in ["clone", "colon", "spoon", "other", "cocoa"] {
+            dict.add_word_str(word);
+        }
+        let mut game = Game::new(dict, "clone", 6);
+        game.set_hard_mode(true);
+        assert!(game.hard_mode());
+        assert_eq!(Err(GuessError::NotInDictionary), game.check_guess("zzzzz"));
+
+        // c is in place, o and l are elsewhere
+        game.guess_word("colon").unwrap();
+        assert_eq!(
+            Err(GuessError::MissingLetter {
+                position: 0,
+                letter: 'c'
+            }),
+            game.check_guess("spoon")
+        );
+        assert_eq!(Err(GuessError::MissingHint('l')), game.check_guess("cocoa"));
+        assert_eq!(None, game.guess_word("cocoa"));
+        assert_eq!(Ok(()), game.check_guess("colon"));
+        assert_eq!(1, game.guess_results.len());
+
+        game.set_hard_mode(false);
+        assert_eq!(Ok(()), game.check_guess("spoon"));
+    }
+
+    #[test]
+    fn test_guess_error_display() {
+        assert_eq!("not a valid word", GuessError::NotInDictionary.to_string());
+        assert_eq!(
+            "letter 4 must be e",
+            GuessError::MissingLetter {
+                position: 3,
+                letter: 'e'
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "guess must contain r",
+            GuessError::MissingHint('r').to_string()
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let mut game = Game::new(basic_dict(), "mat", 2);
+        assert_eq!(
+            Err(SubmitError::WrongLength {
+                letters: 2,
+                expected: 3
+            }),
+            game.submit("ma")
+        );
+        assert_eq!(
+            Err(SubmitError::Invalid(GuessError::NotInDictionary)),
+            game.submit("zzz")
+        );
+        assert!(game.guess_results.is_empty());
+
+        assert_eq!("sat", game.submit("sat").unwrap().word());
+        game.submit("mat").unwrap();
+        assert_eq!(GameState::Win, game.state);
+        assert_eq!(Err(SubmitError::GameOver), game.submit("rat"));
+        assert_eq!(2, game.guess_results.len());
+    }
+
+    #[test]
+    fn test_submit_out_of_time() {
+        let mut game = Game::new(basic_dict(), "mat", 2);
+        game.set_time_limit(Some(Duration::ZERO));
+        assert_eq!(Err(SubmitError::OutOfTime), game.submit("sat"));
+        assert_eq!(GameState::OutOfTime, game.state);
+        assert_eq!(Err(SubmitError::GameOver), game.submit("sat"));
+    }
+
+    #[test]
+    fn test_submit_error_display() {
+        assert_eq!(
+            "guesses must have 3 letters",
+            SubmitError::WrongLength {
+                letters: 2,
+                expected: 3
+            }
+            .to_string()
+        );
+        assert_eq!(
+            "not a valid word",
+            SubmitError::Invalid(GuessError::NotInDictionary).to_string()
+        );
+    }
+
+    #[test]
+    fn test_new_round() {
+        let mut game = Game::new(basic_dict(), "mat", 2);
+        game.guess_word("sat");
+        game.guess_word("rat");
+        assert_eq!(GameState::Lose, game.state);
+
+        game.new_round("sat");
+        assert_eq!(GameState::Playing, game.state);
+        assert_eq!("sat", game.target_word);
+        assert!(game.guess_results.is_empty());
+        assert_eq!(None, game.keyboard_view.get('r'));
+        assert_eq!(None, game.finished);
+
+        game.guess_word("sat");
+        assert_eq!(GameState::Win, game.state);
+    }
+
+    #[test]
+    #[should_panic(expected = "target word not present in dictionary")]
+    fn test_new_round_target_not_in_dictionary() {
+        let mut game = Game::new(basic_dict(), "mat", 2);
+        game.new_round("dog");
+    }
+
+    #[test]
+    fn test_give_up() {
+        let mut game = Game::new(basic_dict(), "mat", 6);
+        game.guess_word("sat");
+        game.give_up();
+        assert_eq!(GameState::Lose, game.state);
+        assert!(game.finished.is_some());
+        assert_eq!(1, game.guess_results.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "game is already over")]
+    fn test_give_up_after_win() {
+        let mut game = Game::new(basic_dict(), "mat", 6);
+        game.guess_word("mat");
+        game.give_up();
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let mut game = Game::new(basic_dict(), "mat", 6);
+        assert_eq!(None, game.time_left());
+        assert!(!game.check_time());
+
+        game.set_time_limit(Some(Duration::from_secs(60)));
+        assert!(game.time_left().unwrap() > Duration::from_secs(59));
+        assert!(!game.check_time());
+        assert_eq!(GameState::Playing, game.state);
+
+        game.set_time_limit(Some(Duration::ZERO));
+        assert_eq!(None, game.guess_word("mat"));
+        assert_eq!(GameState::OutOfTime, game.state);
+        assert_eq!(Duration::ZERO, game.duration());
+        assert_eq!(Some(Duration::ZERO), game.time_left());
+        // only once
+        assert!(!game.check_time());
+    }
+
+    #[test]
+    fn test_time_limit_new_round() {
+        let mut game = Game::new(basic_dict(), "mat", 6);
+        game.set_time_limit(Some(Duration::ZERO));
+        assert!(game.check_time());
+        game.new_round("cat");
+        assert_eq!(GameState::Playing, game.state);
+        assert_eq!(Some(Duration::ZERO), game.time_limit());
+        assert!(game.check_time());
+    }
+
+    #[test]
+    fn test_duration_stops_when_finished() {
+        let mut game = Game::new(basic_dict(), "mat", 6);
+        assert_eq!(None, game.finished);
+        game.guess_word("mat");
+        let duration = game.duration();
+        assert!(game.finished.is_some());
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(duration, game.duration());
+    }
+
     #[test]
     #[should_panic(expected = "target word not present in dictionary")]
     fn test_target_word_length_not_same() {
@@ -1,6 +1,13 @@
+use serde::Serialize;
+use std::cmp;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
 /// A char in a guessed word may be in different states. It may not have been found,
 /// or may be in its correct position, or incorrect position.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum CharState {
     NotFound = 1,
     IncorrectPosition = 2,
@@ -11,7 +18,7 @@ pub enum CharState {
 pub type CharGuess = (char, CharState);
 
 /// Encapsulate state of all characters in a guessed word.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct GuessResult {
     pub char_guesses: Vec<CharGuess>,
 }
@@ -28,6 +35,121 @@ impl GuessResult {
             .into_iter()
             .all(|c| c.1 == CharState::CorrectPosition)
     }
+
+    /// Build a guess result from a guessed word and the colour pattern
+    /// reported for it, e.g. "crane" and "bgybb".
+    /// 'b' (black) means not found, 'y' (yellow) means incorrect position,
+    /// and 'g' (green) means correct position. Casing is ignored.
+    pub fn from_pattern(word: &str, pattern: &str) -> Result<GuessResult, PatternError> {
+        let word_length = word.chars().count();
+        let pattern_length = pattern.chars().count();
+        if word_length != pattern_length {
+            return Err(PatternError::LengthMismatch {
+                word_length,
+                pattern_length,
+            });
+        }
+
+        let mut char_guesses = vec![];
+        for (ch, code) in word.chars().zip(pattern.chars()) {
+            let state = match code.to_ascii_lowercase() {
+                'b' => CharState::NotFound,
+                'y' => CharState::IncorrectPosition,
+                'g' => CharState::CorrectPosition,
+                _ => return Err(PatternError::InvalidCode(code)),
+            };
+            char_guesses.push((ch, state));
+        }
+        Ok(GuessResult::new(char_guesses))
+    }
+
+    /// The guessed word, without the state of each character.
+    pub fn word(&self) -> String {
+        self.char_guesses.iter().map(|c| c.0).collect()
+    }
+}
+
+/// Reasons why a colour pattern could not be parsed into a `GuessResult`.
+#[derive(Debug, PartialEq)]
+pub enum PatternError {
+    LengthMismatch {
+        word_length: usize,
+        pattern_length: usize,
+    },
+    InvalidCode(char),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::LengthMismatch {
+                word_length,
+                pattern_length,
+            } => write!(
+                f,
+                "pattern has {pattern_length} colours but the word has {word_length} letters"
+            ),
+            PatternError::InvalidCode(code) => {
+                write!(f, "unknown colour '{code}', use b, y or g")
+            }
+        }
+    }
+}
+
+/// Computes the guess result for the provided word against the target word.
+/// Both words must have the same length.
+pub fn compute_guess_result(word: &str, target_word: &str) -> GuessResult {
+    let mut char_guesses = vec![];
+
+    for ch in word.chars() {
+        char_guesses.push((ch, CharState::NotFound));
+    }
+
+    let target_map = compute_char_positions_map(target_word);
+    let guess_map = compute_char_positions_map(word);
+
+    for (&ch, target_positions) in &target_map {
+        match guess_map.get(&ch) {
+            None => (),
+            Some(guess_positions) => {
+                let intersection = guess_positions.intersection(target_positions);
+                let mut intersection_len = 0;
+                for correct_position in intersection {
+                    char_guesses[*correct_position] = (ch, CharState::CorrectPosition);
+                    intersection_len += 1;
+                }
+
+                // how many occurrences of ch in target but not in the intersection?
+                let extra_count = target_positions.len() - intersection_len;
+                // positions for this character in guess but not in target
+                let diff = guess_positions.difference(target_positions);
+                // there are extra positions for this character that were not counted in intersection.
+                // there are also some occurences of this character in the guess.
+                // pick extra_count positions from the diff, pick the smaller ones.
+                let mut sorted_diff = diff.copied().collect::<Vec<usize>>();
+                sorted_diff.sort();
+
+                if extra_count > 0 {
+                    let trimmed_length = cmp::min(extra_count, sorted_diff.len());
+                    for incorrect_position in sorted_diff[0..trimmed_length].iter() {
+                        char_guesses[*incorrect_position] = (ch, CharState::IncorrectPosition);
+                    }
+                }
+            }
+        }
+    }
+
+    GuessResult::new(char_guesses)
+}
+
+/// Compute a map of indexes of each character in the word provided.
+fn compute_char_positions_map(word: &str) -> HashMap<char, HashSet<usize>> {
+    let mut map = HashMap::new();
+    for (index, ch) in word.chars().enumerate() {
+        // first occurrence of a character creates an empty set of positions
+        map.entry(ch).or_insert_with(HashSet::new).insert(index);
+    }
+    map
 }
 
 #[cfg(test)]
@@ -57,4 +179,48 @@ mod tests {
         ]);
         assert_eq!(false, gr.is_correct());
     }
+
+    #[test]
+    fn test_from_pattern() {
+        let gr = GuessResult::from_pattern("crane", "bgYbb").unwrap();
+        assert_eq!(
+            GuessResult::new(vec![
+                ('c', CharState::NotFound),
+                ('r', CharState::CorrectPosition),
+                ('a', CharState::IncorrectPosition),
+                ('n', CharState::NotFound),
+                ('e', CharState::NotFound),
+            ]),
+            gr
+        );
+        assert_eq!("crane", gr.word());
+    }
+
+    #[test]
+    fn test_from_pattern_errors() {
+        assert_eq!(
+            Err(PatternError::LengthMismatch {
+                word_length: 5,
+                pattern_length: 4
+            }),
+            GuessResult::from_pattern("crane", "bgyb")
+        );
+        assert_eq!(
+            Err(PatternError::InvalidCode('x')),
+            GuessResult::from_pattern("crane", "bgyxb")
+        );
+    }
+
+    #[test]
+    fn test_compute_guess_result() {
+        assert_eq!(
+            GuessResult::from_pattern("ovolo", "bbgyb").unwrap(),
+            compute_guess_result("ovolo", "clone")
+        );
+        assert_eq!(
+            GuessResult::from_pattern("clone", "gyyyb").unwrap(),
+            compute_guess_result("clone", "colon")
+        );
+        assert!(compute_guess_result("colon", "colon").is_correct());
+    }
 }
@@ -0,0 +1,242 @@
+use serde::Deserialize;
+use std::fmt;
+
+/// Names of the layouts that are built in.
+pub const PRESETS: [&str; 6] = [
+    "qwerty",
+    "azerty",
+    "qwertz",
+    "dvorak",
+    "colemak",
+    "alphabetical",
+];
+
+/// One row of keys, drawn `offset` columns to the right of the first row,
+/// so that rows are staggered like on a physical keyboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyRow {
+    pub keys: Vec<char>,
+    pub offset: usize,
+}
+
+/// Arrangement of the letter keys of the on-screen keyboard.
+/// Only keys that may appear in words are included, so punctuation keys
+/// are left out and rows keep the offset of the letters on a real keyboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    pub name: String,
+    pub rows: Vec<KeyRow>,
+}
+
+/// Problems with the layouts in a config file.
+#[derive(Debug, PartialEq)]
+pub enum LayoutError {
+    /// the file is not valid TOML, or does not have the expected fields
+    Parse(String),
+
+    /// a layout has a different number of offsets than rows
+    OffsetsMismatch(String),
+
+    /// a layout has no keys
+    Empty(String),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::Parse(message) => write!(f, "invalid layouts file: {message}"),
+            LayoutError::OffsetsMismatch(name) => {
+                write!(f, "layout {name} must have one offset per row")
+            }
+            LayoutError::Empty(name) => write!(f, "layout {name} has no keys"),
+        }
+    }
+}
+
+/// Layouts file, e.g.
+///
+/// ```toml
+/// [[layout]]
+/// name = "bepo"
+/// rows = ["bepovdljzw", "auictsrnm", "yxkqghf"]
+/// offsets = [0, 1, 3]
+/// ```
+#[derive(Deserialize)]
+struct LayoutsFile {
+    #[serde(default)]
+    layout: Vec<LayoutEntry>,
+}
+
+#[derive(Deserialize)]
+struct LayoutEntry {
+    name: String,
+    rows: Vec<String>,
+    /// columns each row is shifted by, one per row, defaults to the row number
+    offsets: Option<Vec<usize>>,
+}
+
+impl KeyboardLayout {
+    /// Create a layout from rows of keys, with the offset of each row.
+    pub fn new(name: &str, rows: &[(&str, usize)]) -> KeyboardLayout {
+        KeyboardLayout {
+            name: name.to_string(),
+            rows: rows
+                .iter()
+                .map(|(keys, offset)| KeyRow {
+                    keys: keys.chars().collect(),
+                    offset: *offset,
+                })
+                .collect(),
+        }
+    }
+
+    /// One of the built in layouts, see `PRESETS`.
+    pub fn preset(name: &str) -> Option<KeyboardLayout> {
+        let rows: &[(&str, usize)] = match name {
+            "qwerty" => &[("qwertyuiop", 0), ("asdfghjkl", 1), ("zxcvbnm", 3)],
+            "azerty" => &[("azertyuiop", 0), ("qsdfghjklm", 1), ("wxcvbn", 3)],
+            "qwertz" => &[("qwertzuiop", 0), ("asdfghjkl", 1), ("yxcvbnm", 3)],
+            // the punctuation keys on the left of the top and bottom rows are
+            // left out, so those rows start further right
+            "dvorak" => &[("pyfgcrl", 6), ("aoeuidhtns", 1), ("qjkxbmwvz", 5)],
+            "colemak" => &[("qwfpgjluy", 0), ("arstdhneio", 1), ("zxcvbkm", 3)],
+            "alphabetical" => &[("abcdefghi", 0), ("jklmnopqr", 0), ("stuvwxyz", 0)],
+            _ => return None,
+        };
+        Some(KeyboardLayout::new(name, rows))
+    }
+
+    /// Parse the custom layouts of a config file.
+    pub fn parse_layouts(contents: &str) -> Result<Vec<KeyboardLayout>, LayoutError> {
+        let file: LayoutsFile =
+            toml::from_str(contents).map_err(|err| LayoutError::Parse(err.to_string()))?;
+
+        let mut layouts = vec![];
+        for entry in file.layout {
+            let offsets = entry
+                .offsets
+                .unwrap_or_else(|| (0..entry.rows.len()).collect());
+            if offsets.len() != entry.rows.len() {
+                return Err(LayoutError::OffsetsMismatch(entry.name));
+            }
+            if entry.rows.iter().all(|row| row.is_empty()) {
+                return Err(LayoutError::Empty(entry.name));
+            }
+            let rows: Vec<(&str, usize)> = entry
+                .rows
+                .iter()
+                .map(|row| row.as_str())
+                .zip(offsets)
+                .collect();
+            layouts.push(KeyboardLayout::new(&entry.name, &rows));
+        }
+        Ok(layouts)
+    }
+
+    /// Find a layout by name, among the custom layouts first, then the presets.
+    pub fn find(name: &str, custom: &[KeyboardLayout]) -> Option<KeyboardLayout> {
+        custom
+            .iter()
+            .find(|layout| layout.name == name)
+            .cloned()
+            .or_else(|| KeyboardLayout::preset(name))
+    }
+
+    /// Number of columns needed to draw the layout, with each key two
+    /// columns wide.
+    pub fn width(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.offset + row.keys.len() * 2)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        KeyboardLayout::preset("qwerty").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_presets_have_every_letter_once() {
+        let alphabet: BTreeSet<char> = ('a'..='z').collect();
+        for name in PRESETS {
+            let layout = KeyboardLayout::preset(name).unwrap();
+            assert_eq!(name, layout.name);
+            let keys: Vec<char> = layout.rows.iter().flat_map(|r| r.keys.clone()).collect();
+            assert_eq!(26, keys.len(), "{name}");
+            assert_eq!(alphabet, keys.into_iter().collect(), "{name}");
+        }
+        assert_eq!(None, KeyboardLayout::preset("klingon"));
+    }
+
+    #[test]
+    fn test_width() {
+        let layout = KeyboardLayout::default();
+        assert_eq!("qwerty", layout.name);
+        // "zxcvbnm" at offset 3 is narrower than the top row
+        assert_eq!(20, layout.width());
+    }
+
+    #[test]
+    fn test_parse_layouts() {
+        let contents = r#"
+            [[layout]]
+            name = "bepo"
+            rows = ["bépoèvdljz", "auiectsrnm"]
+            offsets = [0, 1]
+
+            [[layout]]
+            name = "tiny"
+            rows = ["ab", "cd", "ef"]
+        "#;
+        let layouts = KeyboardLayout::parse_layouts(contents).unwrap();
+        assert_eq!(2, layouts.len());
+        assert_eq!("bepo", layouts[0].name);
+        assert_eq!('é', layouts[0].rows[0].keys[1]);
+        assert_eq!(1, layouts[0].rows[1].offset);
+        assert_eq!(
+            vec![0, 1, 2],
+            layouts[1].rows.iter().map(|r| r.offset).collect::<Vec<_>>()
+        );
+        assert!(KeyboardLayout::parse_layouts("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_layout_errors() {
+        let mismatch = "[[layout]]\nname = \"x\"\nrows = [\"ab\"]\noffsets = [0, 1]\n";
+        assert_eq!(
+            Err(LayoutError::OffsetsMismatch("x".to_string())),
+            KeyboardLayout::parse_layouts(mismatch)
+        );
+
+        let empty = "[[layout]]\nname = \"x\"\nrows = []\n";
+        assert_eq!(
+            Err(LayoutError::Empty("x".to_string())),
+            KeyboardLayout::parse_layouts(empty)
+        );
+
+        assert!(matches!(
+            KeyboardLayout::parse_layouts("[[layout]]\nrows = 1\n"),
+            Err(LayoutError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_find() {
+        let custom = vec![KeyboardLayout::new("qwerty", &[("ab", 0)])];
+        assert_eq!(custom[0], KeyboardLayout::find("qwerty", &custom).unwrap());
+        assert_eq!(
+            KeyboardLayout::preset("dvorak"),
+            KeyboardLayout::find("dvorak", &custom)
+        );
+        assert_eq!(None, KeyboardLayout::find("klingon", &custom));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::guess::CharGuess;
 use crate::guess::CharState;
+use crate::guess::GuessResult;
 use std::cmp;
 use std::collections::HashMap;
 
@@ -11,10 +12,19 @@ use std::collections::HashMap;
 /// while the second guess does not find the exact position of the character. In this case,
 /// the keyboard view contains the aggregate state, and it would say that the character
 /// has been found at the correct position.
+/// Within one guess a letter may appear more than once with different states,
+/// e.g. "ovolo" against "clone" finds one 'o' and not the others, so guesses
+/// are recorded as a whole with `record_guess_result`.
 pub struct KeyboardView {
     keymap: HashMap<char, CharState>,
 }
 
+impl Default for KeyboardView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl KeyboardView {
     pub fn new() -> KeyboardView {
         KeyboardView {
@@ -22,6 +32,25 @@ impl KeyboardView {
         }
     }
 
+    /// Record the state of every letter of a guess.
+    /// A letter that appears more than once takes its best state in the
+    /// guess: a copy that is not found only means that the target has no
+    /// more copies of the letter, not that it has none.
+    pub fn record_guess_result(&mut self, guess_result: &GuessResult) {
+        let mut guess_states: Vec<CharGuess> = vec![];
+        for &(ch, state) in guess_result.char_guesses.iter() {
+            match guess_states.iter_mut().find(|(c, _)| *c == ch) {
+                Some(existing) => existing.1 = cmp::max(existing.1, state),
+                None => guess_states.push((ch, state)),
+            }
+        }
+        for char_guess in guess_states.iter() {
+            self.record_guess(char_guess);
+        }
+    }
+
+    /// Record the aggregate state of a letter in one guess.
+    /// panics if the letter was known not to be in the word, and now is.
     pub fn record_guess(&mut self, char_guess: &CharGuess) {
         let ch = char_guess.0;
         let ch_state = &char_guess.1;
@@ -50,6 +79,12 @@ impl KeyboardView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dictionary::Dictionary;
+    use crate::game::Game;
+    use crate::game::GameState;
+    use crate::guess::compute_guess_result;
+    use proptest::prelude::*;
+    use std::sync::OnceLock;
 
     #[test]
     fn test_record_each_state() {
@@ -92,4 +127,92 @@ mod tests {
         keyview.record_guess(&('a', CharState::NotFound));
         keyview.record_guess(&('a', CharState::CorrectPosition));
     }
+
+    #[test]
+    fn test_record_guess_result_duplicate_letters() {
+        // "ovolo" against "clone": only the middle 'o' is found
+        let mut keyview = KeyboardView::new();
+        keyview.record_guess_result(&GuessResult::from_pattern("ovolo", "bbgyb").unwrap());
+        assert_eq!(Some(CharState::CorrectPosition), keyview.get('o'));
+        assert_eq!(Some(CharState::IncorrectPosition), keyview.get('l'));
+        assert_eq!(Some(CharState::NotFound), keyview.get('v'));
+
+        // a later guess with the 'o's misplaced does not revert it
+        keyview.record_guess_result(&GuessResult::from_pattern("colon", "gyyby").unwrap());
+        assert_eq!(Some(CharState::CorrectPosition), keyview.get('o'));
+        assert_eq!(Some(CharState::IncorrectPosition), keyview.get('n'));
+    }
+
+    #[test]
+    fn test_record_guess_result_yellow_and_black() {
+        // "eerie" against "other": one 'e' is in the wrong place
+        let mut keyview = KeyboardView::new();
+        keyview.record_guess_result(&GuessResult::from_pattern("eerie", "bgybb").unwrap());
+        assert_eq!(Some(CharState::CorrectPosition), keyview.get('e'));
+
+        let mut keyview = KeyboardView::new();
+        keyview.record_guess_result(&GuessResult::from_pattern("geese", "bbbbg").unwrap());
+        assert_eq!(Some(CharState::CorrectPosition), keyview.get('e'));
+        assert_eq!(Some(CharState::NotFound), keyview.get('s'));
+    }
+
+    /// Sorted words of the dictionary shipped with the game.
+    fn real_words() -> &'static [String] {
+        static WORDS: OnceLock<Vec<String>> = OnceLock::new();
+        WORDS.get_or_init(|| {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/en-dict-5letters.txt");
+            Dictionary::load(path, 5).unwrap().sorted_words()
+        })
+    }
+
+    proptest! {
+        /// Any game over the real dictionary can be played to the end, and the
+        /// keyboard view agrees with the target word afterwards.
+        #[test]
+        fn prop_valid_games_do_not_panic(
+            target in 0..real_words().len(),
+            guesses in prop::collection::vec(0..real_words().len(), 1..=6),
+        ) {
+            let words = real_words();
+            let mut dict = Dictionary::new(5);
+            let target_word = &words[target];
+            dict.add_word(target_word);
+            for &guess in guesses.iter() {
+                dict.add_word(&words[guess]);
+            }
+
+            let mut game = Game::new(dict, target_word, 6);
+            for &guess in guesses.iter() {
+                if game.state != GameState::Playing {
+                    break;
+                }
+                prop_assert!(game.guess_word(&words[guess]).is_some());
+            }
+
+            for guess_result in game.guess_results.iter() {
+                for &(ch, _) in guess_result.char_guesses.iter() {
+                    let state = game.keyboard_view.get(ch);
+                    prop_assert_eq!(target_word.contains(ch), state != Some(CharState::NotFound));
+                }
+            }
+        }
+
+        /// A guess that repeats letters of the target in every combination of
+        /// positions never panics.
+        #[test]
+        fn prop_repeated_letters_do_not_panic(
+            target in 0..real_words().len(),
+            positions in prop::collection::vec(0..5usize, 5),
+        ) {
+            let target_word: Vec<char> = real_words()[target].chars().collect();
+            let guess: String = positions.iter().map(|&pos| target_word[pos]).collect();
+            let target_word: String = target_word.into_iter().collect();
+
+            let mut keyview = KeyboardView::new();
+            keyview.record_guess_result(&compute_guess_result(&guess, &target_word));
+            for ch in guess.chars() {
+                prop_assert!(keyview.get(ch) > Some(CharState::NotFound));
+            }
+        }
+    }
 }
@@ -0,0 +1,283 @@
+use crate::guess::CharState;
+use crate::guess::GuessResult;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+/// Everything the guesses so far reveal about the target word.
+/// Unlike the `KeyboardView`, which keeps one state per letter, it keeps
+/// the letters that are ruled out at each position, and how many times
+/// each letter may appear in the target.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Knowledge {
+    /// letter known to be at each position
+    fixed: Vec<Option<char>>,
+
+    /// letters known not to be at each position
+    excluded: Vec<BTreeSet<char>>,
+
+    /// least number of times each letter appears in the target
+    min_counts: BTreeMap<char, usize>,
+
+    /// most number of times each letter appears in the target, if known
+    max_counts: BTreeMap<char, usize>,
+}
+
+impl Knowledge {
+    /// Nothing is known yet about a target of the provided length.
+    pub fn new(word_length: usize) -> Knowledge {
+        Knowledge {
+            fixed: vec![None; word_length],
+            excluded: vec![BTreeSet::new(); word_length],
+            min_counts: BTreeMap::new(),
+            max_counts: BTreeMap::new(),
+        }
+    }
+
+    /// What the guess results, all for the same target, reveal about it.
+    pub fn from_guess_results(word_length: usize, guess_results: &[GuessResult]) -> Knowledge {
+        let mut knowledge = Knowledge::new(word_length);
+        for guess_result in guess_results {
+            knowledge.record(guess_result);
+        }
+        knowledge
+    }
+
+    /// Add what one more guess reveals.
+    /// panics if the guess does not have the length of the target.
+    pub fn record(&mut self, guess_result: &GuessResult) {
+        if guess_result.char_guesses.len() != self.word_length() {
+            panic!(
+                "guess has {} chars, expected {}",
+                guess_result.char_guesses.len(),
+                self.word_length()
+            );
+        }
+
+        // (found, not found) occurrences of each letter in this guess
+        let mut occurrences: HashMap<char, (usize, usize)> = HashMap::new();
+        for (pos, &(ch, state)) in guess_result.char_guesses.iter().enumerate() {
+            let entry = occurrences.entry(ch).or_insert((0, 0));
+            match state {
+                CharState::CorrectPosition => {
+                    self.fixed[pos] = Some(ch);
+                    entry.0 += 1;
+                }
+                CharState::IncorrectPosition => {
+                    self.excluded[pos].insert(ch);
+                    entry.0 += 1;
+                }
+                CharState::NotFound => {
+                    self.excluded[pos].insert(ch);
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        for (ch, (found, not_found)) in occurrences {
+            let min = self.min_counts.entry(ch).or_insert(0);
+            *min = (*min).max(found);
+            // a letter marked as not found means the target has no more
+            // copies of it than were found in this guess
+            if not_found > 0 {
+                let max = self.max_counts.entry(ch).or_insert(found);
+                *max = (*max).min(found);
+            }
+        }
+    }
+
+    pub fn word_length(&self) -> usize {
+        self.fixed.len()
+    }
+
+    /// Letter known to be at the position, if any.
+    pub fn fixed(&self, pos: usize) -> Option<char> {
+        self.fixed[pos]
+    }
+
+    /// Least number of times the letter appears in the target.
+    pub fn min_count(&self, ch: char) -> usize {
+        self.min_counts.get(&ch).copied().unwrap_or(0)
+    }
+
+    /// Most number of times the letter appears in the target.
+    /// Returns None if there is no known limit, other than the word length.
+    pub fn max_count(&self, ch: char) -> Option<usize> {
+        self.max_counts.get(&ch).copied()
+    }
+
+    /// Letters whose count is known at all, in alphabetical order, with
+    /// their least and most number of occurrences. Letters known to be
+    /// absent are left out.
+    pub fn letter_counts(&self) -> Vec<(char, usize, Option<usize>)> {
+        self.min_counts
+            .keys()
+            .chain(self.max_counts.keys())
+            .copied()
+            .collect::<BTreeSet<char>>()
+            .into_iter()
+            .map(|ch| (ch, self.min_count(ch), self.max_count(ch)))
+            .filter(|&(_, _, max)| max != Some(0))
+            .collect()
+    }
+
+    /// Check if the letter may still be at the position.
+    pub fn allows(&self, pos: usize, ch: char) -> bool {
+        if let Some(fixed) = self.fixed[pos] {
+            return fixed == ch;
+        }
+        if self.excluded[pos].contains(&ch) {
+            return false;
+        }
+        match self.max_count(ch) {
+            // every allowed copy of the letter is already at a known position
+            Some(max) => self.fixed.iter().filter(|&&f| f == Some(ch)).count() < max,
+            None => true,
+        }
+    }
+
+    /// Letters of the alphabet that may still be at the position.
+    pub fn allowed_letters(
+        &self,
+        pos: usize,
+        alphabet: impl IntoIterator<Item = char>,
+    ) -> Vec<char> {
+        alphabet
+            .into_iter()
+            .filter(|&ch| self.allows(pos, ch))
+            .collect()
+    }
+
+    /// Check if the word may be the target, given everything known about it.
+    pub fn is_consistent(&self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() != self.word_length() {
+            return false;
+        }
+        if chars
+            .iter()
+            .enumerate()
+            .any(|(pos, &ch)| !self.allows(pos, ch))
+        {
+            return false;
+        }
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for &ch in &chars {
+            *counts.entry(ch).or_insert(0) += 1;
+        }
+        let count = |ch: char| counts.get(&ch).copied().unwrap_or(0);
+        self.min_counts.iter().all(|(&ch, &min)| count(ch) >= min)
+            && self.max_counts.iter().all(|(&ch, &max)| count(ch) <= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess::compute_guess_result;
+    use crate::solver;
+
+    fn knowledge(guesses: &[(&str, &str)]) -> Knowledge {
+        let guess_results: Vec<GuessResult> = guesses
+            .iter()
+            .map(|(word, pattern)| GuessResult::from_pattern(word, pattern).unwrap())
+            .collect();
+        Knowledge::from_guess_results(5, &guess_results)
+    }
+
+    #[test]
+    fn test_nothing_known() {
+        let knowledge = Knowledge::new(5);
+        assert!(knowledge.allows(0, 'a'));
+        assert_eq!(0, knowledge.min_count('a'));
+        assert_eq!(None, knowledge.max_count('a'));
+        assert!(knowledge.letter_counts().is_empty());
+        assert!(knowledge.is_consistent("crane"));
+        assert!(!knowledge.is_consistent("cranes"));
+    }
+
+    #[test]
+    fn test_positions() {
+        // target "clone"
+        let knowledge = knowledge(&[("crane", "gbbgg")]);
+        assert_eq!(Some('c'), knowledge.fixed(0));
+        assert_eq!(None, knowledge.fixed(1));
+        assert_eq!(vec!['c'], knowledge.allowed_letters(0, 'a'..='z'));
+        // r and a are not in the target at all
+        assert!(!knowledge.allows(1, 'r'));
+        assert!(!knowledge.allows(2, 'a'));
+        assert!(knowledge.allows(1, 'l'));
+    }
+
+    #[test]
+    fn test_letter_not_in_slot() {
+        // target "other": e is in the word, but not in slot 2
+        let knowledge = knowledge(&[("siena", "bbybb")]);
+        assert!(!knowledge.allows(2, 'e'));
+        assert!(knowledge.allows(3, 'e'));
+        assert_eq!(1, knowledge.min_count('e'));
+        assert_eq!(None, knowledge.max_count('e'));
+        assert!(!knowledge.allows(0, 's'));
+    }
+
+    #[test]
+    fn test_exact_count() {
+        // target "clone": one o at slot 2, the other o's are not found
+        let knowledge = knowledge(&[("ovolo", "bbgyb")]);
+        assert_eq!(1, knowledge.min_count('o'));
+        assert_eq!(Some(1), knowledge.max_count('o'));
+        assert_eq!(
+            vec![('l', 1, None), ('o', 1, Some(1))],
+            knowledge.letter_counts()
+        );
+        // the only o is already placed
+        assert!(!knowledge.allows(3, 'o'));
+        assert!(knowledge.allows(2, 'o'));
+        assert!(knowledge.is_consistent("clone"));
+        assert!(!knowledge.is_consistent("colon"));
+    }
+
+    #[test]
+    fn test_counts_from_several_guesses() {
+        // target "potoo": at least two o's, then exactly three
+        let knowledge = knowledge(&[("spoon", "byygb"), ("ovolo", "ybybg")]);
+        assert_eq!(3, knowledge.min_count('o'));
+        assert_eq!(None, knowledge.max_count('o'));
+        assert!(knowledge.is_consistent("potoo"));
+    }
+
+    #[test]
+    #[should_panic(expected = "guess has 3 chars, expected 5")]
+    fn test_wrong_length() {
+        let mut knowledge = Knowledge::new(5);
+        knowledge.record(&GuessResult::from_pattern("abc", "bbb").unwrap());
+    }
+
+    #[test]
+    fn test_same_as_guess_consistency() {
+        let words = [
+            "clone", "colon", "other", "ovolo", "potoo", "siena", "spoon", "eerie", "geese",
+        ];
+        for target in words {
+            for first in words {
+                for second in words {
+                    let guess_results = vec![
+                        compute_guess_result(first, target),
+                        compute_guess_result(second, target),
+                    ];
+                    let knowledge = Knowledge::from_guess_results(5, &guess_results);
+                    for word in words {
+                        let expected = guess_results
+                            .iter()
+                            .all(|guess_result| solver::is_consistent(word, guess_result));
+                        assert_eq!(
+                            expected,
+                            knowledge.is_consistent(word),
+                            "{word} after {first}, {second} for {target}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,24 @@
+pub mod announce;
+pub mod assistant;
+pub mod board;
+pub mod config;
 pub mod dictionary;
+pub mod difficulty;
 pub mod game;
 pub mod guess;
+pub mod keyboard_layout;
 pub mod keyboard_view;
+pub mod knowledge;
+pub mod openers;
+pub mod pattern;
+pub mod protocol;
+pub mod replay;
+pub mod share;
+pub mod solver;
+pub mod stats;
+pub mod strategy;
+pub mod targets;
+pub mod tournament;
 
 pub use self::dictionary::Dictionary;
 pub use self::game::Game;
@@ -0,0 +1,210 @@
+use crate::solver::Solver;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+/// How good a word is as the first guess of a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenerStats {
+    pub word: String,
+
+    /// expected number of candidates left after the opener
+    pub expected_remaining: f64,
+
+    /// size of the largest group of candidates left after the opener
+    pub worst_case: usize,
+
+    /// chance that at least one char of the opener is in its correct position
+    pub green_chance: f64,
+
+    /// average guesses to find the target, opener included, when the opener
+    /// is followed by the built-in solver. Only computed on request, as it
+    /// plays out every target.
+    pub average_guesses: Option<f64>,
+}
+
+/// Columns the opener report can be sorted by, best opener first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    ExpectedRemaining,
+    WorstCase,
+    GreenChance,
+    AverageGuesses,
+    Word,
+}
+
+/// True if the pattern code has a char in its correct position.
+fn has_green(mut code: u32, word_length: usize) -> bool {
+    for _ in 0..word_length {
+        if code % 3 == 2 {
+            return true;
+        }
+        code /= 3;
+    }
+    false
+}
+
+/// Score one opener against the possible targets.
+pub fn opener_stats(solver: &Solver, opener: usize, targets: &[usize]) -> OpenerStats {
+    let word = solver.words()[opener].clone();
+    let word_length = word.chars().count();
+    let buckets = solver.buckets(opener, targets);
+
+    let total = targets.len().max(1) as f64;
+    let sum_squares: usize = buckets.values().map(|&size| size * size).sum();
+    let greens: usize = buckets
+        .iter()
+        .filter(|(&code, _)| has_green(code, word_length))
+        .map(|(_, &size)| size)
+        .sum();
+
+    OpenerStats {
+        word,
+        expected_remaining: sum_squares as f64 / total,
+        worst_case: buckets.values().copied().max().unwrap_or(0),
+        green_chance: greens as f64 / total,
+        average_guesses: None,
+    }
+}
+
+/// Average number of guesses the solver needs to find each target, when it
+/// starts with the opener.
+pub fn average_guesses(solver: &Solver, opener: usize, targets: &[usize]) -> f64 {
+    if targets.is_empty() {
+        return 0.0;
+    }
+    let guesses = solver.guesses_per_target(Some(opener), targets);
+    let total: usize = guesses.iter().map(|&(_, n)| n).sum();
+    total as f64 / targets.len() as f64
+}
+
+/// Score every word of the solver as an opener against every word as a
+/// target, sorted by expected remaining candidates. The average guesses are
+/// only computed for the first `simulate_top` openers of that order.
+pub fn rank_openers(solver: &Solver, simulate_top: usize) -> Vec<OpenerStats> {
+    let targets = solver.all_candidates();
+    let mut stats: Vec<OpenerStats> = targets
+        .par_iter()
+        .map(|&opener| opener_stats(solver, opener, &targets))
+        .collect();
+    sort_openers(&mut stats, SortKey::ExpectedRemaining);
+
+    for opener_stats in stats.iter_mut().take(simulate_top) {
+        let opener = solver
+            .index_of(&opener_stats.word)
+            .expect("opener is one of the solver's words");
+        opener_stats.average_guesses = Some(average_guesses(solver, opener, &targets));
+    }
+    stats
+}
+
+/// Sort the openers by the key, best first. Ties are broken by word, and
+/// openers without average guesses come last when sorting by them.
+pub fn sort_openers(stats: &mut [OpenerStats], key: SortKey) {
+    stats.sort_by(|a, b| {
+        let ordering = match key {
+            SortKey::ExpectedRemaining => a.expected_remaining.total_cmp(&b.expected_remaining),
+            SortKey::WorstCase => a.worst_case.cmp(&b.worst_case),
+            SortKey::GreenChance => b.green_chance.total_cmp(&a.green_chance),
+            SortKey::AverageGuesses => match (a.average_guesses, b.average_guesses) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortKey::Word => Ordering::Equal,
+        };
+        ordering.then_with(|| a.word.cmp(&b.word))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern;
+
+    fn solver() -> Solver {
+        let words = [
+            "clone", "colon", "other", "ovolo", "potoo", "siena", "spoon",
+        ];
+        Solver::new(words.iter().map(|w| w.to_string()).collect())
+    }
+
+    #[test]
+    fn test_has_green() {
+        assert!(has_green(pattern::correct_code(5), 5));
+        assert!(!has_green(0, 5));
+        // "byb" is 0 + 1 * 3 + 0 * 9
+        assert!(!has_green(3, 3));
+        // "bbg" is 2 * 9
+        assert!(has_green(18, 3));
+    }
+
+    #[test]
+    fn test_opener_stats() {
+        let solver = Solver::new(vec![
+            "bat".to_string(),
+            "cat".to_string(),
+            "dog".to_string(),
+        ]);
+        let stats = opener_stats(&solver, 0, &solver.all_candidates());
+        // "bat" splits the targets into {bat}, {cat}, {dog}
+        assert_eq!("bat", stats.word);
+        assert_eq!(1.0, stats.expected_remaining);
+        assert_eq!(1, stats.worst_case);
+        assert_eq!(2.0 / 3.0, stats.green_chance);
+        assert_eq!(None, stats.average_guesses);
+
+        // "dog" splits them into {dog}, {bat, cat}
+        let stats = opener_stats(&solver, 2, &solver.all_candidates());
+        assert_eq!(5.0 / 3.0, stats.expected_remaining);
+        assert_eq!(2, stats.worst_case);
+        assert_eq!(1.0 / 3.0, stats.green_chance);
+    }
+
+    #[test]
+    fn test_average_guesses() {
+        let solver = Solver::new(vec![
+            "bat".to_string(),
+            "cat".to_string(),
+            "dog".to_string(),
+        ]);
+        // "bat" is found at once, "cat" and "dog" with the second guess
+        assert_eq!(
+            5.0 / 3.0,
+            average_guesses(&solver, 0, &solver.all_candidates())
+        );
+    }
+
+    #[test]
+    fn test_rank_openers() {
+        let solver = solver();
+        let stats = rank_openers(&solver, 2);
+        assert_eq!(solver.words().len(), stats.len());
+        assert!(stats
+            .windows(2)
+            .all(|w| w[0].expected_remaining <= w[1].expected_remaining));
+        assert!(stats[0].average_guesses.is_some());
+        assert!(stats[1].average_guesses.is_some());
+        assert!(stats[2..].iter().all(|s| s.average_guesses.is_none()));
+    }
+
+    #[test]
+    fn test_sort_openers() {
+        let mut stats = rank_openers(&solver(), 1);
+        let best = stats[0].word.clone();
+
+        sort_openers(&mut stats, SortKey::Word);
+        assert_eq!("clone", stats[0].word);
+
+        sort_openers(&mut stats, SortKey::AverageGuesses);
+        assert_eq!(best, stats[0].word);
+
+        sort_openers(&mut stats, SortKey::GreenChance);
+        assert!(stats
+            .windows(2)
+            .all(|w| w[0].green_chance >= w[1].green_chance));
+
+        sort_openers(&mut stats, SortKey::WorstCase);
+        assert!(stats.windows(2).all(|w| w[0].worst_case <= w[1].worst_case));
+    }
+}
@@ -0,0 +1,381 @@
+use crate::guess::CharState;
+use crate::guess::GuessResult;
+use memmap2::Mmap;
+use rayon::prelude::*;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::ops::Deref;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Longest word whose feedback pattern fits into a u8 code, 3^5 = 243 patterns.
+pub const MAX_MATRIX_WORD_LENGTH: usize = 5;
+
+/// Longest word whose feedback pattern fits into a u32 code.
+pub const MAX_CODE_WORD_LENGTH: usize = 20;
+
+/// Identifies pattern matrix cache files, followed by the format version.
+const CACHE_MAGIC: &[u8; 4] = b"WSPM";
+const CACHE_VERSION: u32 = 1;
+const CACHE_HEADER_LENGTH: usize = 4 + 4 + 8 + 8;
+
+/// Numeric value of a char state in a pattern code.
+fn state_digit(state: CharState) -> u32 {
+    match state {
+        CharState::NotFound => 0,
+        CharState::IncorrectPosition => 1,
+        CharState::CorrectPosition => 2,
+    }
+}
+
+/// Encode a guess result as a base 3 number. The state of the first char is
+/// the least significant digit: 0 for not found, 1 for incorrect position
+/// and 2 for correct position.
+pub fn pattern_code(guess_result: &GuessResult) -> u32 {
+    guess_result
+        .char_guesses
+        .iter()
+        .rev()
+        .fold(0, |code, char_guess| code * 3 + state_digit(char_guess.1))
+}
+
+/// Code of the pattern where every char is in its correct position.
+pub fn correct_code(word_length: usize) -> u32 {
+    3u32.pow(word_length as u32) - 1
+}
+
+/// Compute the pattern code of a guess against a target without building a
+/// `GuessResult`. Same result as `pattern_code(&compute_guess_result(..))`,
+/// but without any allocations, since it is called for every pair of words.
+/// panics if the words are longer than `MAX_CODE_WORD_LENGTH`.
+pub fn compute_pattern_code(guess: &[char], target: &[char]) -> u32 {
+    let length = guess.len();
+    if length > MAX_CODE_WORD_LENGTH {
+        panic!("words longer than {MAX_CODE_WORD_LENGTH} chars are not supported");
+    }
+
+    let mut digits = [0u32; MAX_CODE_WORD_LENGTH];
+    // chars of the target that were not matched at their correct position
+    let mut unmatched = ['\0'; MAX_CODE_WORD_LENGTH];
+    let mut unmatched_len = 0;
+    for i in 0..length {
+        if guess[i] == target[i] {
+            digits[i] = 2;
+        } else {
+            unmatched[unmatched_len] = target[i];
+            unmatched_len += 1;
+        }
+    }
+
+    // leftmost occurrences in the guess are marked as incorrect position first
+    for i in 0..length {
+        if digits[i] == 2 {
+            continue;
+        }
+        if let Some(pos) = unmatched[..unmatched_len]
+            .iter()
+            .position(|&ch| ch == guess[i])
+        {
+            digits[i] = 1;
+            unmatched[pos] = unmatched[unmatched_len - 1];
+            unmatched_len -= 1;
+        }
+    }
+
+    digits[..length]
+        .iter()
+        .rev()
+        .fold(0, |code, digit| code * 3 + digit)
+}
+
+/// Feedback pattern of every guess against every possible answer, stored as
+/// one u8 code per pair. Both guesses and answers are the words of a
+/// dictionary, in sorted order.
+pub struct PatternMatrix {
+    words: Vec<String>,
+    codes: Codes,
+}
+
+/// Storage of the codes, either computed in memory or mapped from a cache file.
+enum Codes {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Deref for Codes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Codes::Owned(codes) => codes,
+            Codes::Mapped(mmap) => &mmap[CACHE_HEADER_LENGTH..],
+        }
+    }
+}
+
+impl PatternMatrix {
+    /// Compute the matrix for the provided sorted words, in parallel.
+    /// panics if the words are longer than `MAX_MATRIX_WORD_LENGTH`.
+    pub fn build(words: Vec<String>) -> PatternMatrix {
+        let chars: Vec<Vec<char>> = words.iter().map(|w| w.chars().collect()).collect();
+        if chars.iter().any(|w| w.len() > MAX_MATRIX_WORD_LENGTH) {
+            panic!("pattern matrix only supports words of up to {MAX_MATRIX_WORD_LENGTH} chars");
+        }
+
+        let count = words.len();
+        let mut codes = vec![0u8; count * count];
+        if count > 0 {
+            codes
+                .par_chunks_mut(count)
+                .enumerate()
+                .for_each(|(guess, row)| {
+                    for (answer, code) in row.iter_mut().enumerate() {
+                        *code = compute_pattern_code(&chars[guess], &chars[answer]) as u8;
+                    }
+                });
+        }
+
+        PatternMatrix {
+            words,
+            codes: Codes::Owned(codes),
+        }
+    }
+
+    /// Memory-map the matrix for the provided sorted words from the cache
+    /// directory, or build it and save it there if it is not cached yet.
+    /// The cache file is keyed by a hash of the words. Also returns whether
+    /// the matrix could be saved: a matrix that was built is kept even if it
+    /// could not be cached.
+    pub fn load_or_build(words: Vec<String>, cache_dir: &Path) -> (PatternMatrix, io::Result<()>) {
+        let path = PatternMatrix::cache_path(&words, cache_dir);
+        if let Ok(matrix) = PatternMatrix::load(&words, &path) {
+            return (matrix, Ok(()));
+        }
+
+        let matrix = PatternMatrix::build(words);
+        let saved = matrix.save(&path);
+        (matrix, saved)
+    }
+
+    /// Default directory for cache files, following the XDG base directory spec.
+    pub fn default_cache_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("wordle-star"))
+    }
+
+    /// Path of the cache file for the provided words.
+    pub fn cache_path(words: &[String], cache_dir: &Path) -> PathBuf {
+        cache_dir.join(format!("patterns-{:016x}.bin", hash_words(words)))
+    }
+
+    /// Memory-map a matrix saved earlier, checking that it matches the words.
+    fn load(words: &[String], path: &Path) -> io::Result<PatternMatrix> {
+        let file = File::open(path)?;
+        // safe as long as the cache file is not modified while mapped. It is
+        // only ever written to a temporary file that is then renamed.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let count = words.len();
+        if mmap.len() != CACHE_HEADER_LENGTH + count * count
+            || &mmap[0..4] != CACHE_MAGIC
+            || mmap[4..8] != CACHE_VERSION.to_le_bytes()
+            || mmap[8..16] != (count as u64).to_le_bytes()
+            || mmap[16..24] != hash_words(words).to_le_bytes()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "pattern matrix cache does not match the dictionary",
+            ));
+        }
+
+        Ok(PatternMatrix {
+            words: words.to_vec(),
+            codes: Codes::Mapped(mmap),
+        })
+    }
+
+    /// Save the matrix to a cache file.
+    fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let tmp_path = path.with_extension(format!("tmp{}", std::process::id()));
+        let mut file = io::BufWriter::new(File::create(&tmp_path)?);
+        file.write_all(CACHE_MAGIC)?;
+        file.write_all(&CACHE_VERSION.to_le_bytes())?;
+        file.write_all(&(self.words.len() as u64).to_le_bytes())?;
+        file.write_all(&hash_words(&self.words).to_le_bytes())?;
+        file.write_all(&self.codes)?;
+        file.flush()?;
+        drop(file);
+
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Words of the matrix, in sorted order. Indexes refer to this list.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Index of the word in the matrix, if present.
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.words.binary_search_by(|w| w.as_str().cmp(word)).ok()
+    }
+
+    /// Pattern code of the guess against the answer, both given by index.
+    pub fn get(&self, guess: usize, answer: usize) -> u8 {
+        self.codes[guess * self.words.len() + answer]
+    }
+
+    /// Pattern codes of the guess against every answer.
+    pub fn row(&self, guess: usize) -> &[u8] {
+        let count = self.words.len();
+        &self.codes[guess * count..(guess + 1) * count]
+    }
+
+    /// Was the matrix mapped from a cache file?
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.codes, Codes::Mapped(_))
+    }
+}
+
+/// FNV-1a hash of the words. Unlike the std hasher, it is stable across
+/// releases of Rust, so it can be used to name cache files.
+fn hash_words(words: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in words {
+        for byte in word.bytes().chain(std::iter::once(b'\n')) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess::compute_guess_result;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn big_words() -> Vec<String> {
+        words(&[
+            "clone", "colon", "other", "ovolo", "potoo", "siena", "spoon",
+        ])
+    }
+
+    /// Unique directory under the system temp dir for a test.
+    fn temp_cache_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("wordle-star-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_pattern_code() {
+        let gr = GuessResult::from_pattern("crane", "bgybb").unwrap();
+        // digits from the last char to the first: 0 0 1 2 0
+        assert_eq!(9 + 6, pattern_code(&gr));
+        assert_eq!(242, correct_code(5));
+        assert_eq!(
+            correct_code(5),
+            pattern_code(&compute_guess_result("crane", "crane"))
+        );
+    }
+
+    #[test]
+    fn test_compute_pattern_code_matches_guess_result() {
+        let words = big_words();
+        for guess in words.iter() {
+            for target in words.iter() {
+                let guess_chars: Vec<char> = guess.chars().collect();
+                let target_chars: Vec<char> = target.chars().collect();
+                assert_eq!(
+                    pattern_code(&compute_guess_result(guess, target)),
+                    compute_pattern_code(&guess_chars, &target_chars),
+                    "guess: {guess}, target: {target}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let matrix = PatternMatrix::build(big_words());
+        assert!(!matrix.is_mapped());
+        assert_eq!(Some(1), matrix.index_of("colon"));
+        assert_eq!(None, matrix.index_of("crane"));
+
+        let clone = matrix.index_of("clone").unwrap();
+        let colon = matrix.index_of("colon").unwrap();
+        assert_eq!(
+            pattern_code(&compute_guess_result("clone", "colon")) as u8,
+            matrix.get(clone, colon)
+        );
+        assert_eq!(correct_code(5) as u8, matrix.get(clone, clone));
+        assert_eq!(matrix.get(clone, colon), matrix.row(clone)[colon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "pattern matrix only supports words of up to 5 chars")]
+    fn test_build_long_words() {
+        PatternMatrix::build(words(&["abcdef"]));
+    }
+
+    #[test]
+    fn test_load_or_build_caches() {
+        let dir = temp_cache_dir("cache");
+        let (built, saved) = PatternMatrix::load_or_build(big_words(), &dir);
+        assert!(saved.is_ok());
+        assert!(!built.is_mapped());
+        assert!(PatternMatrix::cache_path(&big_words(), &dir).exists());
+
+        let (mapped, _) = PatternMatrix::load_or_build(big_words(), &dir);
+        assert!(mapped.is_mapped());
+        for guess in 0..big_words().len() {
+            assert_eq!(built.row(guess), mapped.row(guess));
+        }
+
+        // a different dictionary does not reuse the cache
+        let (other, _) = PatternMatrix::load_or_build(words(&["clone", "colon"]), &dir);
+        assert!(!other.is_mapped());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_rebuilt() {
+        let dir = temp_cache_dir("corrupt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(PatternMatrix::cache_path(&big_words(), &dir), b"garbage").unwrap();
+
+        let (matrix, _) = PatternMatrix::load_or_build(big_words(), &dir);
+        assert!(!matrix.is_mapped());
+        let (mapped, _) = PatternMatrix::load_or_build(big_words(), &dir);
+        assert!(mapped.is_mapped());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unwritable_cache_keeps_matrix() {
+        let dir = temp_cache_dir("unwritable");
+        fs::create_dir_all(&dir).unwrap();
+        // the cache directory cannot be created below a file
+        let file = dir.join("file");
+        fs::write(&file, b"").unwrap();
+
+        let (matrix, saved) = PatternMatrix::load_or_build(big_words(), &file.join("cache"));
+        assert!(saved.is_err());
+        assert_eq!(big_words().len(), matrix.words().len());
+        assert!(!matrix.is_mapped());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
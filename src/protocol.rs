@@ -0,0 +1,216 @@
+use crate::game::{Game, GameState, SubmitError};
+use crate::guess::{CharState, GuessResult};
+use serde::{Deserialize, Serialize};
+
+/// A message from a client of the headless mode, one JSON object per line,
+/// e.g. {"type": "guess", "word": "crane"}. Other fields are ignored.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Request {
+    /// start the next game, once the current one is over
+    NewGame,
+    Guess {
+        word: String,
+    },
+    /// the guesses of the current game so far
+    State,
+}
+
+impl Request {
+    /// Parse a line of input.
+    pub fn parse(line: &str) -> Result<Request, Response> {
+        serde_json::from_str(line).map_err(|err| Response::error(format!("invalid request: {err}")))
+    }
+}
+
+/// A message to the client, one JSON object per line. The target word is
+/// only sent once the game is over.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Response {
+    /// a game was started
+    NewGame {
+        word_length: usize,
+        max_attempts: usize,
+        hard_mode: bool,
+    },
+    /// the result of a guess that was accepted
+    Guess {
+        guess: Guess,
+        remaining_attempts: usize,
+        state: GameState,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+    },
+    State {
+        guesses: Vec<Guess>,
+        remaining_attempts: usize,
+        state: GameState,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+    },
+    /// a request that could not be handled, which changes nothing
+    Error { message: String },
+}
+
+/// A guess and the state of each of its letters.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Guess {
+    pub word: String,
+    pub letters: Vec<Letter>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Letter {
+    pub letter: char,
+    pub state: CharState,
+}
+
+impl From<&GuessResult> for Guess {
+    fn from(guess_result: &GuessResult) -> Guess {
+        Guess {
+            word: guess_result.word(),
+            letters: guess_result
+                .char_guesses
+                .iter()
+                .map(|&(letter, state)| Letter { letter, state })
+                .collect(),
+        }
+    }
+}
+
+impl Response {
+    pub fn error(message: impl Into<String>) -> Response {
+        Response::Error {
+            message: message.into(),
+        }
+    }
+
+    /// Announce a game that was just started.
+    pub fn new_game(game: &Game) -> Response {
+        Response::NewGame {
+            word_length: game.word_length(),
+            max_attempts: game.max_guesses(),
+            hard_mode: game.hard_mode(),
+        }
+    }
+
+    /// Submit a guess to the game. A guess made after the time limit ends
+    /// the game, and gets its state in return.
+    pub fn guess(game: &mut Game, word: &str) -> Response {
+        let word = word.to_lowercase();
+        match game.submit(&word) {
+            Ok(guess_result) => Response::Guess {
+                guess: Guess::from(&guess_result),
+                remaining_attempts: game.view().remaining_guesses(),
+                state: game.state,
+                target: revealed_target(game),
+            },
+            Err(SubmitError::GameOver) => Response::error("the game is over, start a new game"),
+            Err(SubmitError::OutOfTime) => Response::state(game),
+            Err(err) => Response::error(format!("{word}: {err}")),
+        }
+    }
+
+    /// The guesses of the game so far.
+    pub fn state(game: &Game) -> Response {
+        Response::State {
+            guesses: game.guess_results.iter().map(Guess::from).collect(),
+            remaining_attempts: game.view().remaining_guesses(),
+            state: game.state,
+            target: revealed_target(game),
+        }
+    }
+
+    /// This response as a line of JSON, without the line break.
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self).expect("responses serialize to JSON")
+    }
+}
+
+/// The target word, once the game is over.
+fn revealed_target(game: &Game) -> Option<String> {
+    (game.state != GameState::Playing).then(|| game.target_word.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::Dictionary;
+
+    fn game() -> Game {
+        let mut dict = Dictionary::new(3);
+        for word in ["cat", "bat", "rat", "tab"] {
+            dict.add_word_str(word);
+        }
+        Game::new(dict, "cat", 2)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(Request::NewGame),
+            Request::parse(r#"{"type": "new-game"}"#)
+        );
+        assert_eq!(
+            Ok(Request::Guess {
+                word: "cat".to_string()
+            }),
+            Request::parse(r#"{"type": "guess", "word": "cat"}"#)
+        );
+        for line in ["guess cat", r#"{"type": "guess"}"#, r#"{"type": "peek"}"#] {
+            assert!(matches!(Request::parse(line), Err(Response::Error { .. })));
+        }
+    }
+
+    #[test]
+    fn test_target_hidden_until_the_end() {
+        let mut game = game();
+        let line = Response::guess(&mut game, "tab").to_line();
+        assert_eq!(
+            r#"{"type":"guess","guess":{"word":"tab","letters":[{"letter":"t","state":"incorrect-position"},{"letter":"a","state":"correct-position"},{"letter":"b","state":"not-found"}]},"remaining_attempts":1,"state":"playing"}"#,
+            line
+        );
+        assert!(!Response::state(&game).to_line().contains("cat"));
+
+        let line = Response::guess(&mut game, "rat").to_line();
+        assert!(line.contains(r#""remaining_attempts":0,"state":"lose","target":"cat"}"#));
+        assert!(Response::state(&game)
+            .to_line()
+            .contains(r#""target":"cat""#));
+    }
+
+    #[test]
+    fn test_invalid_guesses() {
+        let mut game = game();
+        assert_eq!(
+            Response::error("ca: guesses must have 3 letters"),
+            Response::guess(&mut game, "ca")
+        );
+        assert_eq!(
+            Response::error("cow: not a valid word"),
+            Response::guess(&mut game, "cow")
+        );
+        assert!(game.guess_results.is_empty());
+
+        assert!(matches!(
+            Response::guess(&mut game, "CAT"),
+            Response::Guess {
+                state: GameState::Win,
+                ..
+            }
+        ));
+        assert_eq!(
+            Response::error("the game is over, start a new game"),
+            Response::guess(&mut game, "bat")
+        );
+    }
+
+    #[test]
+    fn test_new_game() {
+        assert_eq!(
+            r#"{"type":"new-game","word_length":3,"max_attempts":2,"hard_mode":false}"#,
+            Response::new_game(&game()).to_line()
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use crate::board::Board;
+use crate::dictionary::Dictionary;
+use crate::game::Game;
+use crate::guess::GuessResult;
+use crate::keyboard_view::KeyboardView;
+use crate::stats::{GameRecord, Outcome};
+use std::fmt;
+
+/// Why a recorded game cannot be replayed, usually because it was played
+/// with another dictionary.
+#[derive(Debug, PartialEq)]
+pub enum ReplayError {
+    TargetNotInDictionary(String),
+    InvalidGuess(String),
+    /// the target was guessed before the last guess
+    GuessesAfterTarget,
+    /// more guesses than the game allows
+    TooManyGuesses(usize),
+    /// the result does not follow from the guesses, e.g. a win whose last
+    /// guess is not the target
+    WrongOutcome,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::TargetNotInDictionary(word) => {
+                write!(f, "target {word} is not in the dictionary")
+            }
+            ReplayError::InvalidGuess(word) => write!(f, "guess {word} is not in the dictionary"),
+            ReplayError::GuessesAfterTarget => write!(f, "guesses follow the target"),
+            ReplayError::TooManyGuesses(count) => {
+                write!(f, "{count} guesses, more than the game allows")
+            }
+            ReplayError::WrongOutcome => write!(f, "the result does not match the guesses"),
+        }
+    }
+}
+
+/// A recorded game played again one guess at a time. The game is rebuilt
+/// by submitting the recorded guesses, so that every step looks exactly
+/// like it did when the game was played.
+pub struct Replay {
+    record: GameRecord,
+    game: Game,
+}
+
+impl Replay {
+    /// Replay a recorded game, starting before the first guess, with as
+    /// many rows as the game allowed guesses. Records that do not say how
+    /// many, from older versions, get at least max_guesses rows.
+    pub fn new(
+        record: GameRecord,
+        dict: Dictionary,
+        max_guesses: usize,
+    ) -> Result<Replay, ReplayError> {
+        if !dict.contains(&record.target) {
+            return Err(ReplayError::TargetNotInDictionary(record.target));
+        }
+        if let Some(word) = record.guesses.iter().find(|word| !dict.contains(word)) {
+            return Err(ReplayError::InvalidGuess(word.clone()));
+        }
+        let before_last = &record.guesses[..record.guesses.len().saturating_sub(1)];
+        if before_last.contains(&record.target) {
+            return Err(ReplayError::GuessesAfterTarget);
+        }
+        let max_guesses = match record.max_guesses {
+            Some(allowed) => allowed,
+            None => max_guesses.max(record.guesses.len()),
+        };
+        if record.guesses.len() > max_guesses {
+            return Err(ReplayError::TooManyGuesses(record.guesses.len()));
+        }
+        // games may be lost with fewer guesses, by giving up
+        let found = record.guesses.last() == Some(&record.target);
+        if found != (record.result == Outcome::Win) {
+            return Err(ReplayError::WrongOutcome);
+        }
+        let game = Game::new(dict, &record.target, max_guesses);
+        Ok(Replay { record, game })
+    }
+
+    pub fn record(&self) -> &GameRecord {
+        &self.record
+    }
+
+    /// The game after the guesses of the steps so far.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Number of guesses shown so far.
+    pub fn step(&self) -> usize {
+        self.game.guess_results.len()
+    }
+
+    /// Number of guesses of the game.
+    pub fn steps(&self) -> usize {
+        self.record.guesses.len()
+    }
+
+    /// Show the next guess. Returns false if all of them are shown.
+    pub fn forward(&mut self) -> bool {
+        match self.record.guesses.get(self.step()) {
+            Some(word) => {
+                // checked against the dictionary when the replay was made
+                self.game.guess_word(word).expect("recorded guess is valid");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Hide the last guess shown. Returns false if none are shown.
+    pub fn back(&mut self) -> bool {
+        let step = self.step();
+        if step == 0 {
+            return false;
+        }
+        self.go_to(step - 1);
+        true
+    }
+
+    /// Show the first guesses, from the start of the game.
+    /// panics if the game has fewer guesses.
+    pub fn go_to(&mut self, step: usize) {
+        if step > self.steps() {
+            panic!("recorded game has only {} guesses", self.steps());
+        }
+        self.game.new_round(&self.record.target);
+        for _ in 0..step {
+            self.forward();
+        }
+    }
+}
+
+impl Board for Replay {
+    fn guess_results(&self) -> &[GuessResult] {
+        self.game.guess_results()
+    }
+
+    fn keyboard_view(&self) -> &KeyboardView {
+        self.game.keyboard_view()
+    }
+
+    fn word_length(&self) -> usize {
+        Board::word_length(&self.game)
+    }
+
+    fn max_guesses(&self) -> Option<usize> {
+        Board::max_guesses(&self.game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+    use chrono::NaiveDate;
+
+    fn dict() -> Dictionary {
+        let mut dict = Dictionary::new(3);
+        for word in ["cat", "bat", "rat", "tab"] {
+            dict.add_word_str(word);
+        }
+        dict
+    }
+
+    /// A game won with its last guess, or lost otherwise, recorded by a
+    /// version that did not keep the guesses allowed.
+    fn record(target: &str, guesses: &[&str]) -> GameRecord {
+        let result = if guesses.last() == Some(&target) {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        };
+        GameRecord {
+            date: NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            target: target.to_string(),
+            guesses: guesses.iter().map(|word| word.to_string()).collect(),
+            max_guesses: None,
+            result,
+            mode: "play".to_string(),
+            duration_secs: 42,
+        }
+    }
+
+    #[test]
+    fn test_step_through() {
+        let mut replay = Replay::new(record("cat", &["tab", "bat", "cat"]), dict(), 6).unwrap();
+        assert_eq!((0, 3), (replay.step(), replay.steps()));
+        assert!(!replay.back());
+
+        assert!(replay.forward());
+        assert!(replay.forward());
+        assert_eq!(2, replay.step());
+        assert_eq!("bat", replay.guess_results()[1].word());
+        assert_eq!(GameState::Playing, replay.game().state);
+
+        assert!(replay.forward());
+        assert!(!replay.forward());
+        assert_eq!(GameState::Win, replay.game().state);
+
+        assert!(replay.back());
+        assert_eq!(2, replay.step());
+        assert_eq!(GameState::Playing, replay.game().state);
+        assert_eq!(Some(6), replay.max_guesses());
+    }
+
+    #[test]
+    fn test_keyboard_is_rebuilt() {
+        let mut played = Game::new(dict(), "cat", 6);
+        played.guess_word("tab");
+        played.guess_word("rat");
+
+        let mut replay = Replay::new(record("cat", &["tab", "rat", "cat"]), dict(), 6).unwrap();
+        replay.go_to(3);
+        replay.go_to(2);
+        for ch in ['t', 'a', 'b', 'r', 'c'] {
+            assert_eq!(
+                played.keyboard_view().get(ch),
+                replay.keyboard_view().get(ch)
+            );
+        }
+    }
+
+    #[test]
+    fn test_other_dictionary() {
+        assert_eq!(
+            Some(ReplayError::TargetNotInDictionary("dog".to_string())),
+            Replay::new(record("dog", &["cat"]), dict(), 6).err()
+        );
+        assert_eq!(
+            Some(ReplayError::InvalidGuess("cow".to_string())),
+            Replay::new(record("cat", &["cow", "cat"]), dict(), 6).err()
+        );
+        assert_eq!(
+            "guess cow is not in the dictionary",
+            ReplayError::InvalidGuess("cow".to_string()).to_string()
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_record() {
+        assert_eq!(
+            Some(ReplayError::GuessesAfterTarget),
+            Replay::new(record("cat", &["cat", "bat"]), dict(), 6).err()
+        );
+        let too_many = GameRecord {
+            max_guesses: Some(2),
+            ..record("cat", &["tab", "bat", "cat"])
+        };
+        assert_eq!(
+            Some(ReplayError::TooManyGuesses(3)),
+            Replay::new(too_many, dict(), 6).err()
+        );
+
+        let not_won = GameRecord {
+            result: Outcome::Win,
+            ..record("cat", &["tab", "bat"])
+        };
+        assert_eq!(
+            Some(ReplayError::WrongOutcome),
+            Replay::new(not_won, dict(), 6).err()
+        );
+        let not_lost = GameRecord {
+            result: Outcome::OutOfTime,
+            ..record("cat", &["tab", "cat"])
+        };
+        assert_eq!(
+            Some(ReplayError::WrongOutcome),
+            Replay::new(not_lost, dict(), 6).err()
+        );
+        // given up
+        assert!(Replay::new(record("cat", &["tab", "bat"]), dict(), 6).is_ok());
+    }
+
+    #[test]
+    fn test_guesses_allowed() {
+        let longer = GameRecord {
+            max_guesses: Some(8),
+            ..record("cat", &["tab", "bat", "cat"])
+        };
+        let replay = Replay::new(longer, dict(), 6).unwrap();
+        assert_eq!(Some(8), replay.max_guesses());
+
+        // older records get enough rows for their guesses
+        let replay = Replay::new(record("cat", &["tab", "bat", "cat"]), dict(), 2).unwrap();
+        assert_eq!(Some(3), replay.max_guesses());
+    }
+
+    #[test]
+    #[should_panic(expected = "recorded game has only 1 guesses")]
+    fn test_go_past_the_end() {
+        Replay::new(record("cat", &["cat"]), dict(), 6)
+            .unwrap()
+            .go_to(2);
+    }
+}
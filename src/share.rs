@@ -0,0 +1,140 @@
+use crate::game::Game;
+use crate::game::GameState;
+use crate::guess::CharState;
+use chrono::NaiveDate;
+
+/// Name of the game at the start of the share text.
+pub const SHARE_TITLE: &str = "Wordle Star";
+
+/// Symbols used for each char of a guess in the share text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShareStyle {
+    /// 🟩 🟨 ⬛, like the original game
+    Standard,
+    /// 🟧 🟦 ⬛, for players who cannot tell green from yellow
+    HighContrast,
+    /// G Y -, for places that do not show emoji
+    Ascii,
+}
+
+impl ShareStyle {
+    fn symbol(&self, state: CharState) -> &'static str {
+        match (self, state) {
+            (ShareStyle::Standard, CharState::CorrectPosition) => "🟩",
+            (ShareStyle::Standard, CharState::IncorrectPosition) => "🟨",
+            (ShareStyle::HighContrast, CharState::CorrectPosition) => "🟧",
+            (ShareStyle::HighContrast, CharState::IncorrectPosition) => "🟦",
+            (ShareStyle::Standard | ShareStyle::HighContrast, CharState::NotFound) => "⬛",
+            (ShareStyle::Ascii, CharState::CorrectPosition) => "G",
+            (ShareStyle::Ascii, CharState::IncorrectPosition) => "Y",
+            (ShareStyle::Ascii, CharState::NotFound) => "-",
+        }
+    }
+}
+
+/// Number of the daily puzzle of the date, counting from the first wordle.
+pub fn puzzle_number(date: NaiveDate) -> i64 {
+    let first = NaiveDate::from_ymd_opt(2021, 6, 19).unwrap();
+    (date - first).num_days()
+}
+
+/// Text to share the result of a finished game without revealing the
+/// target: a header like "Wordle Star 1234 4/6*", where X is a loss and a
+/// star marks hard mode, followed by one row of symbols per guess. Games
+/// other than the daily puzzle are labelled "practice" instead of a number.
+/// panics if the game is still being played.
+pub fn share_text(
+    game: &Game,
+    puzzle_number: Option<i64>,
+    hard_mode: bool,
+    style: ShareStyle,
+) -> String {
+    let score = match game.state {
+        GameState::Win => game.guess_results.len().to_string(),
+        GameState::Lose | GameState::OutOfTime => "X".to_string(),
+        GameState::Playing => panic!("game is not finished"),
+    };
+    let puzzle = match puzzle_number {
+        Some(number) => number.to_string(),
+        None => "practice".to_string(),
+    };
+    let mut text = format!(
+        "{SHARE_TITLE} {puzzle} {score}/{}{}\n",
+        game.max_guesses(),
+        if hard_mode { "*" } else { "" }
+    );
+    for guess_result in game.guess_results.iter() {
+        text.push('\n');
+        for &(_, state) in guess_result.char_guesses.iter() {
+            text.push_str(style.symbol(state));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::Dictionary;
+
+    fn game(guesses: &[&str]) -> Game {
+        let mut dict = Dictionary::new(5);
+        for word in ["clone", "colon", "spoon", "other"] {
+            dict.add_word_str(word);
+        }
+        let mut game = Game::new(dict, "clone", 3);
+        for guess in guesses {
+            game.guess_word(guess);
+        }
+        game
+    }
+
+    #[test]
+    fn test_puzzle_number() {
+        assert_eq!(
+            0,
+            puzzle_number(NaiveDate::from_ymd_opt(2021, 6, 19).unwrap())
+        );
+        assert_eq!(
+            366,
+            puzzle_number(NaiveDate::from_ymd_opt(2022, 6, 20).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_share_win() {
+        let game = game(&["spoon", "colon", "clone"]);
+        assert_eq!(
+            "Wordle Star 7 3/3\n\n⬛⬛🟩⬛🟨\n🟩🟨🟨⬛🟨\n🟩🟩🟩🟩🟩",
+            share_text(&game, Some(7), false, ShareStyle::Standard)
+        );
+    }
+
+    #[test]
+    fn test_share_styles() {
+        let game = game(&["other", "spoon", "colon"]);
+        assert_eq!(
+            "Wordle Star 7 X/3*\n\n🟦⬛⬛🟦⬛\n⬛⬛🟧⬛🟦\n🟧🟦🟦⬛🟦",
+            share_text(&game, Some(7), true, ShareStyle::HighContrast)
+        );
+        assert_eq!(
+            "Wordle Star 7 X/3\n\nY--Y-\n--G-Y\nGYY-Y",
+            share_text(&game, Some(7), false, ShareStyle::Ascii)
+        );
+    }
+
+    #[test]
+    fn test_share_practice() {
+        let game = game(&["colon", "clone"]);
+        assert_eq!(
+            "Wordle Star practice 2/3\n\nGYY-Y\nGGGGG",
+            share_text(&game, None, false, ShareStyle::Ascii)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "game is not finished")]
+    fn test_share_unfinished() {
+        share_text(&game(&["spoon"]), None, false, ShareStyle::Ascii);
+    }
+}
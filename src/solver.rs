@@ -0,0 +1,477 @@
+use crate::guess::compute_guess_result;
+use crate::guess::GuessResult;
+use crate::pattern;
+use crate::pattern::PatternMatrix;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::sync::OnceLock;
+
+/// Upper bound on the number of (guess, candidate) pairs scored when picking
+/// the next guess without a pattern matrix. Beyond this, fewer guesses are
+/// scored, or a cheaper letter frequency heuristic is used.
+const MAX_SCORED_PAIRS: usize = 250_000;
+
+/// Check if a word could be the target, given the result of an earlier guess.
+pub fn is_consistent(word: &str, guess_result: &GuessResult) -> bool {
+    compute_guess_result(&guess_result.word(), word) == *guess_result
+}
+
+/// Picks guesses that narrow down the possible targets as fast as possible.
+/// Words are referred to by their index in the sorted word list. Feedback
+/// patterns are looked up in a `PatternMatrix` when one is available, and
+/// computed on the fly otherwise.
+pub struct Solver {
+    /// all words that may be guessed or be the target, in sorted order
+    words: Vec<String>,
+
+    /// chars of each word, to compute patterns without a matrix
+    chars: Vec<Vec<char>>,
+
+    /// precomputed patterns of every pair of words
+    matrix: Option<PatternMatrix>,
+
+    /// best first guess, computed once since it is the most expensive one
+    opener: OnceLock<Option<usize>>,
+}
+
+impl Solver {
+    /// Create a solver that computes patterns on the fly.
+    /// The words must be in sorted order.
+    /// panics if the words are longer than `pattern::MAX_CODE_WORD_LENGTH`.
+    pub fn new(words: Vec<String>) -> Solver {
+        let chars: Vec<Vec<char>> = words.iter().map(|w| w.chars().collect()).collect();
+        if chars
+            .iter()
+            .any(|w| w.len() > pattern::MAX_CODE_WORD_LENGTH)
+        {
+            panic!(
+                "solver only supports words of up to {} chars",
+                pattern::MAX_CODE_WORD_LENGTH
+            );
+        }
+        Solver {
+            words,
+            chars,
+            matrix: None,
+            opener: OnceLock::new(),
+        }
+    }
+
+    /// Create a solver that looks up patterns in a precomputed matrix.
+    pub fn with_matrix(matrix: PatternMatrix) -> Solver {
+        let mut solver = Solver::new(matrix.words().to_vec());
+        solver.matrix = Some(matrix);
+        solver
+    }
+
+    /// Create a solver backed by the pattern matrix in the default cache
+    /// directory, building and caching the matrix on first use. Words too
+    /// long for a matrix, or a missing cache directory, give a solver that
+    /// computes patterns on the fly. Also returns whether the matrix could
+    /// be cached, see `PatternMatrix::load_or_build`.
+    pub fn with_cached_matrix(words: Vec<String>) -> (Solver, io::Result<()>) {
+        let too_long = words
+            .iter()
+            .any(|w| w.chars().count() > pattern::MAX_MATRIX_WORD_LENGTH);
+        match PatternMatrix::default_cache_dir() {
+            Some(cache_dir) if !too_long => {
+                let (matrix, saved) = PatternMatrix::load_or_build(words, &cache_dir);
+                (Solver::with_matrix(matrix), saved)
+            }
+            _ => (Solver::new(words), Ok(())),
+        }
+    }
+
+    /// All words known to the solver, in sorted order.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Index of the word, if known to the solver.
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.words.binary_search_by(|w| w.as_str().cmp(word)).ok()
+    }
+
+    /// Indexes of every word, i.e. the candidates before any guess.
+    pub fn all_candidates(&self) -> Vec<usize> {
+        (0..self.words.len()).collect()
+    }
+
+    /// Pattern code of the guess against the answer, both given by index.
+    pub fn pattern(&self, guess: usize, answer: usize) -> u32 {
+        match &self.matrix {
+            Some(matrix) => matrix.get(guess, answer) as u32,
+            None => pattern::compute_pattern_code(&self.chars[guess], &self.chars[answer]),
+        }
+    }
+
+    /// Keep only the candidates that are consistent with the guess result.
+    /// The guessed word does not have to be known to the solver.
+    pub fn filter(&self, candidates: &[usize], guess_result: &GuessResult) -> Vec<usize> {
+        let code = pattern::pattern_code(guess_result);
+        match self.index_of(&guess_result.word()) {
+            Some(guess) => candidates
+                .iter()
+                .copied()
+                .filter(|&answer| self.pattern(guess, answer) == code)
+                .collect(),
+            None => {
+                let guess_chars: Vec<char> = guess_result.word().chars().collect();
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|&answer| {
+                        pattern::compute_pattern_code(&guess_chars, &self.chars[answer]) == code
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Pick the next guess given the words that may still be the target.
+    /// Guesses are scored by the expected number of candidates left after
+    /// playing them, lower is better. Candidates win ties, since they may
+    /// solve the puzzle right away.
+    /// Returns None if there are no candidates left.
+    pub fn best_guess(&self, candidates: &[usize]) -> Option<usize> {
+        if candidates.len() == self.words.len() {
+            return *self
+                .opener
+                .get_or_init(|| self.compute_best_guess(candidates));
+        }
+        self.compute_best_guess(candidates)
+    }
+
+    fn compute_best_guess(&self, candidates: &[usize]) -> Option<usize> {
+        if candidates.len() <= 2 {
+            return candidates.first().copied();
+        }
+
+        let all_words = self.all_candidates();
+        let pool =
+            if self.matrix.is_some() || self.words.len() * candidates.len() <= MAX_SCORED_PAIRS {
+                &all_words[..]
+            } else if candidates.len() * candidates.len() <= MAX_SCORED_PAIRS {
+                candidates
+            } else {
+                return self.most_frequent_letters(candidates);
+            };
+
+        self.lowest_score(pool, candidates)
+    }
+
+    /// Pick the next guess among the candidates only, as in hard mode: a
+    /// word that may still be the target uses every letter revealed so far.
+    /// Returns None if there are no candidates left.
+    pub fn best_candidate(&self, candidates: &[usize]) -> Option<usize> {
+        if candidates.len() == self.words.len() {
+            return self.best_guess(candidates);
+        }
+        if candidates.len() <= 2 {
+            return candidates.first().copied();
+        }
+        if self.matrix.is_none() && candidates.len() * candidates.len() > MAX_SCORED_PAIRS {
+            return self.most_frequent_letters(candidates);
+        }
+        self.lowest_score(candidates, candidates)
+    }
+
+    /// The guess of the pool that leaves the fewest candidates expected.
+    fn lowest_score(&self, pool: &[usize], candidates: &[usize]) -> Option<usize> {
+        let candidate_set: HashSet<usize> = candidates.iter().copied().collect();
+        // (score, is not a candidate, index), the smallest one is the best guess
+        pool.par_iter()
+            .map(|&guess| {
+                (
+                    self.bucket_score(guess, candidates),
+                    !candidate_set.contains(&guess),
+                    guess,
+                )
+            })
+            .min()
+            .map(|(_, _, guess)| guess)
+    }
+
+    /// Sizes of the groups the candidates are split into by the pattern of
+    /// the guess, keyed by pattern code.
+    pub fn buckets(&self, guess: usize, candidates: &[usize]) -> HashMap<u32, usize> {
+        let mut buckets = HashMap::new();
+        for &answer in candidates {
+            *buckets.entry(self.pattern(guess, answer)).or_insert(0) += 1;
+        }
+        buckets
+    }
+
+    /// Groups the candidates are split into by the pattern of the guess,
+    /// keyed by pattern code.
+    pub fn partition(&self, guess: usize, candidates: &[usize]) -> HashMap<u32, Vec<usize>> {
+        let mut groups: HashMap<u32, Vec<usize>> = HashMap::new();
+        for &answer in candidates {
+            groups
+                .entry(self.pattern(guess, answer))
+                .or_default()
+                .push(answer);
+        }
+        groups
+    }
+
+    /// Number of guesses the solver needs to find each of the candidates,
+    /// starting with the provided guess, or with its own best guess if None.
+    /// Returns (candidate, guesses) pairs, in no particular order.
+    /// There is no limit on the number of guesses.
+    pub fn guesses_per_target(
+        &self,
+        first_guess: Option<usize>,
+        candidates: &[usize],
+    ) -> Vec<(usize, usize)> {
+        let guess = match first_guess.or_else(|| self.best_guess(candidates)) {
+            Some(guess) => guess,
+            None => return vec![],
+        };
+
+        // groups are independent of each other, solve them in parallel
+        let groups: Vec<(u32, Vec<usize>)> =
+            self.partition(guess, candidates).into_iter().collect();
+        groups
+            .par_iter()
+            .flat_map_iter(|(code, group)| {
+                let mut result = vec![];
+                self.solve_group(guess, *code, group, 1, &mut result);
+                result
+            })
+            .collect()
+    }
+
+    /// Record the guesses needed for a group of candidates that all got the
+    /// same pattern for the last guess, made at the provided depth.
+    fn solve_group(
+        &self,
+        guess: usize,
+        code: u32,
+        group: &[usize],
+        depth: usize,
+        result: &mut Vec<(usize, usize)>,
+    ) {
+        if code == pattern::correct_code(self.chars[guess].len()) {
+            result.push((guess, depth));
+            return;
+        }
+
+        // the best guess always splits the group, since a candidate would
+        // get a bucket of its own and so score better than a guess that
+        // leaves the group whole: the recursion ends
+        let next_guess = match self.best_guess(group) {
+            Some(next_guess) => next_guess,
+            None => return,
+        };
+        for (next_code, next_group) in self.partition(next_guess, group) {
+            self.solve_group(next_guess, next_code, &next_group, depth + 1, result);
+        }
+    }
+
+    /// Sum of squared bucket sizes when the candidates are split by the
+    /// pattern of the guess. Proportional to the expected number of
+    /// remaining candidates.
+    fn bucket_score(&self, guess: usize, candidates: &[usize]) -> usize {
+        match &self.matrix {
+            Some(matrix) => {
+                // patterns fit in a u8, count them without hashing
+                let row = matrix.row(guess);
+                let mut buckets = [0usize; 256];
+                for &answer in candidates {
+                    buckets[row[answer] as usize] += 1;
+                }
+                buckets.iter().map(|size| size * size).sum()
+            }
+            None => self
+                .buckets(guess, candidates)
+                .values()
+                .map(|size| size * size)
+                .sum(),
+        }
+    }
+
+    /// Pick the candidate whose distinct letters are the most common among
+    /// all candidates, counting letters in the same position twice.
+    /// Much cheaper than `best_guess`, and closer to how people play.
+    pub fn most_frequent_letters(&self, candidates: &[usize]) -> Option<usize> {
+        let mut letter_counts: HashMap<char, usize> = HashMap::new();
+        let mut position_counts: HashMap<(usize, char), usize> = HashMap::new();
+        for &word in candidates {
+            let distinct: HashSet<char> = self.chars[word].iter().copied().collect();
+            for ch in distinct {
+                *letter_counts.entry(ch).or_insert(0) += 1;
+            }
+            for (pos, &ch) in self.chars[word].iter().enumerate() {
+                *position_counts.entry((pos, ch)).or_insert(0) += 1;
+            }
+        }
+
+        let score = |word: usize| -> usize {
+            let distinct: HashSet<char> = self.chars[word].iter().copied().collect();
+            let letters: usize = distinct.iter().map(|ch| letter_counts[ch]).sum();
+            let positions: usize = self.chars[word]
+                .iter()
+                .enumerate()
+                .map(|(pos, &ch)| position_counts[&(pos, ch)])
+                .sum();
+            letters + positions
+        };
+
+        // words are sorted, so ties go to the first word in the list
+        candidates
+            .iter()
+            .copied()
+            .max_by(|&a, &b| score(a).cmp(&score(b)).then(b.cmp(&a)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn indexes(solver: &Solver, list: &[&str]) -> Vec<usize> {
+        list.iter().map(|w| solver.index_of(w).unwrap()).collect()
+    }
+
+    fn big_words() -> Vec<String> {
+        words(&[
+            "clone", "colon", "other", "ovolo", "potoo", "siena", "spoon",
+        ])
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let gr = GuessResult::from_pattern("clone", "gyyyb").unwrap();
+        assert!(is_consistent("colon", &gr));
+        assert!(!is_consistent("clone", &gr));
+    }
+
+    #[test]
+    fn test_filter() {
+        let solver = Solver::new(big_words());
+        let gr = GuessResult::from_pattern("spoon", "bbygg").unwrap();
+        assert_eq!(
+            indexes(&solver, &["colon"]),
+            solver.filter(&solver.all_candidates(), &gr)
+        );
+
+        // guessed word is unknown to the solver
+        let gr = GuessResult::from_pattern("crane", "gbbgg").unwrap();
+        assert_eq!(
+            indexes(&solver, &["clone"]),
+            solver.filter(&solver.all_candidates(), &gr)
+        );
+    }
+
+    #[test]
+    fn test_filter_with_matrix() {
+        let solver = Solver::with_matrix(PatternMatrix::build(big_words()));
+        let gr = GuessResult::from_pattern("spoon", "bbygg").unwrap();
+        assert_eq!(
+            indexes(&solver, &["colon"]),
+            solver.filter(&solver.all_candidates(), &gr)
+        );
+    }
+
+    #[test]
+    fn test_best_guess_no_candidates() {
+        let solver = Solver::new(words(&["abc"]));
+        assert_eq!(None, solver.best_guess(&[]));
+    }
+
+    #[test]
+    fn test_best_guess_few_candidates() {
+        let solver = Solver::new(words(&["bat", "cat"]));
+        assert_eq!(Some(0), solver.best_guess(&solver.all_candidates()));
+    }
+
+    #[test]
+    fn test_best_guess_splits_candidates() {
+        // "bcd" is not a candidate, but it separates all of them
+        let solver = Solver::new(words(&["bat", "bcd", "cat", "dat"]));
+        let candidates = indexes(&solver, &["bat", "cat", "dat"]);
+        assert_eq!(solver.index_of("bcd"), solver.best_guess(&candidates));
+        assert_eq!(solver.index_of("bat"), solver.best_candidate(&candidates));
+    }
+
+    #[test]
+    fn test_best_guess_prefers_candidates_on_ties() {
+        // "abc" and "zzc" both leave buckets of sizes 1 and 2
+        let solver = Solver::new(words(&["abc", "abd", "abe", "zzc"]));
+        let candidates = indexes(&solver, &["abc", "abd", "abe"]);
+        assert_eq!(solver.index_of("abc"), solver.best_guess(&candidates));
+    }
+
+    #[test]
+    fn test_best_guess_same_with_matrix() {
+        let solver = Solver::new(big_words());
+        let matrix_solver = Solver::with_matrix(PatternMatrix::build(big_words()));
+        let candidates = solver.all_candidates();
+        assert_eq!(
+            solver.best_guess(&candidates),
+            matrix_solver.best_guess(&candidates)
+        );
+    }
+
+    #[test]
+    fn test_buckets() {
+        let solver = Solver::new(words(&["bat", "cat", "dat"]));
+        let buckets = solver.buckets(0, &solver.all_candidates());
+        assert_eq!(2, buckets.len());
+        assert_eq!(Some(&1), buckets.get(&pattern::correct_code(3)));
+    }
+
+    #[test]
+    fn test_partition() {
+        let solver = Solver::new(words(&["bat", "cat", "dat"]));
+        let groups = solver.partition(0, &solver.all_candidates());
+        assert_eq!(Some(&vec![0]), groups.get(&pattern::correct_code(3)));
+        assert_eq!(2, groups.len());
+    }
+
+    #[test]
+    fn test_guesses_per_target() {
+        let solver = Solver::new(words(&["bat", "bcd", "cat", "dat"]));
+        let candidates = indexes(&solver, &["bat", "cat", "dat"]);
+
+        // "bcd" separates the candidates, each is found with the second guess
+        let mut guesses = solver.guesses_per_target(solver.index_of("bcd"), &candidates);
+        guesses.sort();
+        assert_eq!(vec![(0, 2), (2, 2), (3, 2)], guesses);
+
+        // "bat" is found right away, then "cat" and "dat" need up to two more
+        let mut guesses = solver.guesses_per_target(solver.index_of("bat"), &candidates);
+        guesses.sort();
+        assert_eq!(vec![(0, 1), (2, 2), (3, 3)], guesses);
+    }
+
+    #[test]
+    fn test_guesses_per_target_finds_all() {
+        let solver = Solver::new(big_words());
+        let guesses = solver.guesses_per_target(None, &solver.all_candidates());
+        assert_eq!(big_words().len(), guesses.len());
+        assert!(guesses.iter().all(|&(_, n)| (1..=4).contains(&n)));
+    }
+
+    #[test]
+    fn test_most_frequent_letters() {
+        let solver = Solver::new(words(&["abc", "abd", "xyz"]));
+        assert_eq!(
+            Some(0),
+            solver.most_frequent_letters(&solver.all_candidates())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "solver only supports words of up to 20 chars")]
+    fn test_long_words() {
+        Solver::new(words(&["abcdefghijklmnopqrstu"]));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::game::Game;
+use crate::game::GameState;
+use chrono::Local;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Shortest guess distribution shown, even if no game took that many guesses.
+pub const MIN_DISTRIBUTION_LENGTH: usize = 6;
+
+/// How a finished game ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Win,
+    Lose,
+
+    /// lost because the time limit passed
+    #[serde(rename = "out-of-time")]
+    OutOfTime,
+}
+
+/// One finished game, as kept in the stats store.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// local date the game was finished on
+    pub date: NaiveDate,
+    pub target: String,
+
+    /// words guessed, in order
+    pub guesses: Vec<String>,
+
+    /// guesses the game allowed, not kept by older versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_guesses: Option<usize>,
+    pub result: Outcome,
+
+    /// how the target was picked, e.g. "play"
+    pub mode: String,
+    pub duration_secs: u64,
+}
+
+impl GameRecord {
+    /// Record of a finished game, played today.
+    /// panics if the game is still being played.
+    pub fn from_game(game: &Game, mode: &str) -> GameRecord {
+        let result = match game.state {
+            GameState::Win => Outcome::Win,
+            GameState::Lose => Outcome::Lose,
+            GameState::OutOfTime => Outcome::OutOfTime,
+            GameState::Playing => panic!("game is not finished"),
+        };
+        GameRecord {
+            date: Local::now().date_naive(),
+            target: game.target_word.clone(),
+            guesses: game.guess_results.iter().map(|gr| gr.word()).collect(),
+            max_guesses: Some(game.max_guesses()),
+            result,
+            mode: mode.to_string(),
+            duration_secs: game.duration().as_secs(),
+        }
+    }
+}
+
+/// Finished games, kept in a file with one JSON record per line so that
+/// recording a game only appends to it.
+pub struct StatsStore {
+    path: PathBuf,
+}
+
+impl StatsStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> StatsStore {
+        StatsStore {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Store in the user's data directory, e.g. ~/.local/share/wordle-star.
+    /// Returns None if the platform has no data directory.
+    pub fn open_default() -> Option<StatsStore> {
+        dirs::data_dir().map(|dir| StatsStore::new(dir.join("wordle-star").join("stats.jsonl")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a finished game to the store, creating it if needed.
+    pub fn record(&self, record: &GameRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let line = serde_json::to_string(record)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")
+    }
+
+    /// All games in the store, oldest first. A store that does not exist yet
+    /// has no games. Lines that cannot be read, e.g. from a write that was
+    /// cut short, are skipped rather than losing the rest of the history.
+    pub fn load(&self) -> io::Result<Vec<GameRecord>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+        Ok(contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+/// Totals over all the games of a store.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub played: usize,
+    pub wins: usize,
+
+    /// games won in a row, up to the last game
+    pub current_streak: usize,
+    pub max_streak: usize,
+
+    /// number of games won in exactly n guesses, at index n - 1
+    pub distribution: Vec<usize>,
+}
+
+impl Stats {
+    /// Totals of the games, which must be ordered oldest first.
+    pub fn from_records(records: &[GameRecord]) -> Stats {
+        let mut stats = Stats {
+            played: 0,
+            wins: 0,
+            current_streak: 0,
+            max_streak: 0,
+            distribution: vec![0; MIN_DISTRIBUTION_LENGTH],
+        };
+        for record in records {
+            stats.played += 1;
+            match record.result {
+                Outcome::Win => {
+                    stats.wins += 1;
+                    stats.current_streak += 1;
+                    stats.max_streak = stats.max_streak.max(stats.current_streak);
+
+                    let guesses = record.guesses.len().max(1);
+                    if stats.distribution.len() < guesses {
+                        stats.distribution.resize(guesses, 0);
+                    }
+                    stats.distribution[guesses - 1] += 1;
+                }
+                Outcome::Lose | Outcome::OutOfTime => stats.current_streak = 0,
+            }
+        }
+        stats
+    }
+
+    /// Percentage of games won, between 0 and 100.
+    pub fn win_percentage(&self) -> f64 {
+        if self.played == 0 {
+            return 0.0;
+        }
+        self.wins as f64 * 100.0 / self.played as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::Dictionary;
+
+    fn record(result: Outcome, guesses: usize) -> GameRecord {
+        GameRecord {
+            date: NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            target: "crane".to_string(),
+            guesses: vec!["crane".to_string(); guesses],
+            max_guesses: Some(6),
+            result,
+            mode: "play".to_string(),
+            duration_secs: 42,
+        }
+    }
+
+    fn temp_store(name: &str) -> StatsStore {
+        let dir =
+            std::env::temp_dir().join(format!("wordle-star-stats-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        StatsStore::new(dir.join("nested").join("stats.jsonl"))
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let store = temp_store("load");
+        assert_eq!(Vec::<GameRecord>::new(), store.load().unwrap());
+
+        let first = record(Outcome::Win, 3);
+        let second = record(Outcome::Lose, 6);
+        store.record(&first).unwrap();
+        store.record(&second).unwrap();
+        assert_eq!(vec![first.clone(), second], store.load().unwrap());
+
+        // a broken line does not lose the rest of the history
+        let mut file = OpenOptions::new().append(true).open(store.path()).unwrap();
+        writeln!(file, "{{\"date\": \"2024-").unwrap();
+        store.record(&first).unwrap();
+        assert_eq!(3, store.load().unwrap().len());
+
+        fs::remove_dir_all(store.path().parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_record_format() {
+        let json = serde_json::to_string(&record(Outcome::Win, 1)).unwrap();
+        assert_eq!(
+            r#"{"date":"2024-02-29","target":"crane","guesses":["crane"],"max_guesses":6,"result":"win","mode":"play","duration_secs":42}"#,
+            json
+        );
+    }
+
+    #[test]
+    fn test_record_without_max_guesses() {
+        let line = r#"{"date":"2024-02-29","target":"crane","guesses":["crane"],"result":"win","mode":"play","duration_secs":42}"#;
+        let record: GameRecord = serde_json::from_str(line).unwrap();
+        assert_eq!(None, record.max_guesses);
+    }
+
+    #[test]
+    fn test_from_game() {
+        let mut dict = Dictionary::new(3);
+        dict.add_word_str("cat");
+        dict.add_word_str("bat");
+        let mut game = Game::new(dict, "cat", 6);
+        game.guess_word("bat");
+        game.guess_word("cat");
+
+        let record = GameRecord::from_game(&game, "play");
+        assert_eq!("cat", record.target);
+        assert_eq!(vec!["bat".to_string(), "cat".to_string()], record.guesses);
+        assert_eq!(Some(6), record.max_guesses);
+        assert_eq!(Outcome::Win, record.result);
+        assert_eq!("play", record.mode);
+    }
+
+    #[test]
+    #[should_panic(expected = "game is not finished")]
+    fn test_from_unfinished_game() {
+        let mut dict = Dictionary::new(3);
+        dict.add_word_str("cat");
+        GameRecord::from_game(&Game::new(dict, "cat", 6), "play");
+    }
+
+    #[test]
+    fn test_stats() {
+        let records = vec![
+            record(Outcome::Win, 3),
+            record(Outcome::Win, 4),
+            record(Outcome::Win, 3),
+            record(Outcome::Lose, 6),
+            record(Outcome::Win, 8),
+        ];
+        let stats = Stats::from_records(&records);
+        assert_eq!(5, stats.played);
+        assert_eq!(4, stats.wins);
+        assert_eq!(80.0, stats.win_percentage());
+        assert_eq!(1, stats.current_streak);
+        assert_eq!(3, stats.max_streak);
+        assert_eq!(vec![0, 0, 2, 1, 0, 0, 0, 1], stats.distribution);
+    }
+
+    #[test]
+    fn test_out_of_time() {
+        let records = vec![record(Outcome::Win, 3), record(Outcome::OutOfTime, 2)];
+        let stats = Stats::from_records(&records);
+        assert_eq!((2, 1, 0), (stats.played, stats.wins, stats.current_streak));
+        assert!(serde_json::to_string(&records[1])
+            .unwrap()
+            .contains(r#""result":"out-of-time""#));
+    }
+
+    #[test]
+    fn test_no_stats() {
+        let stats = Stats::from_records(&[]);
+        assert_eq!(0, stats.played);
+        assert_eq!(0.0, stats.win_percentage());
+        assert_eq!(vec![0; MIN_DISTRIBUTION_LENGTH], stats.distribution);
+    }
+}
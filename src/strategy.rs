@@ -0,0 +1,170 @@
+use crate::game::GameView;
+use crate::solver::Solver;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::rc::Rc;
+
+/// A player that decides on the next guess of a game.
+/// Strategies only see a `GameView`, so they cannot peek at the target word,
+/// and are told the seed of each game so that random choices are repeatable.
+pub trait Strategy {
+    /// Name of the strategy, shown on the leaderboard.
+    fn name(&self) -> String;
+
+    /// Called before the first guess of each game.
+    fn new_game(&mut self, _seed: u64) {}
+
+    /// The word to guess next. Words that are not in the dictionary are
+    /// rejected by the game without using up a guess.
+    fn next_guess(&mut self, view: &GameView) -> String;
+}
+
+/// Indexes of the words that are consistent with every guess of the game.
+fn remaining_candidates(solver: &Solver, view: &GameView) -> Vec<usize> {
+    view.guess_results
+        .iter()
+        .fold(solver.all_candidates(), |candidates, guess_result| {
+            solver.filter(&candidates, guess_result)
+        })
+}
+
+/// Plays the guess suggested by the built-in solver, which minimizes the
+/// expected number of remaining candidates. In hard mode it only plays
+/// words that may still be the target, which the game always accepts.
+pub struct SolverStrategy {
+    solver: Rc<Solver>,
+}
+
+impl SolverStrategy {
+    pub fn new(solver: Rc<Solver>) -> SolverStrategy {
+        SolverStrategy { solver }
+    }
+}
+
+impl Strategy for SolverStrategy {
+    fn name(&self) -> String {
+        "solver".to_string()
+    }
+
+    fn next_guess(&mut self, view: &GameView) -> String {
+        let candidates = remaining_candidates(&self.solver, view);
+        let best = if view.hard_mode {
+            self.solver.best_candidate(&candidates)
+        } else {
+            self.solver.best_guess(&candidates)
+        };
+        match best {
+            Some(guess) => self.solver.words()[guess].to_string(),
+            // the target is not among the solver's words, give up gracefully
+            None => self.solver.words()[0].to_string(),
+        }
+    }
+}
+
+/// Plays a random word that is consistent with every guess so far, like a
+/// player who knows the rules but does not plan ahead.
+pub struct RandomCandidateStrategy {
+    solver: Rc<Solver>,
+    rng: StdRng,
+}
+
+impl RandomCandidateStrategy {
+    pub fn new(solver: Rc<Solver>) -> RandomCandidateStrategy {
+        RandomCandidateStrategy {
+            solver,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+}
+
+impl Strategy for RandomCandidateStrategy {
+    fn name(&self) -> String {
+        "random-candidate".to_string()
+    }
+
+    fn new_game(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn next_guess(&mut self, view: &GameView) -> String {
+        let candidates = remaining_candidates(&self.solver, view);
+        let guess = candidates.choose(&mut self.rng).copied().unwrap_or(0);
+        self.solver.words()[guess].to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guess::GuessResult;
+
+    fn solver() -> Rc<Solver> {
+        let words = [
+            "clone", "colon", "other", "ovolo", "potoo", "siena", "spoon",
+        ];
+        Rc::new(Solver::new(words.iter().map(|w| w.to_string()).collect()))
+    }
+
+    #[test]
+    fn test_solver_strategy() {
+        let mut strategy = SolverStrategy::new(solver());
+        let guess_results = vec![GuessResult::from_pattern("spoon", "bbygg").unwrap()];
+        let view = GameView {
+            guess_results: &guess_results,
+            max_guesses: 6,
+            word_length: 5,
+            hard_mode: false,
+        };
+        assert_eq!("colon", strategy.next_guess(&view));
+    }
+
+    #[test]
+    fn test_random_candidate_strategy_is_repeatable() {
+        let view = GameView {
+            guess_results: &[],
+            max_guesses: 6,
+            word_length: 5,
+            hard_mode: false,
+        };
+
+        let mut strategy = RandomCandidateStrategy::new(solver());
+        strategy.new_game(42);
+        let first: Vec<String> = (0..5).map(|_| strategy.next_guess(&view)).collect();
+        strategy.new_game(42);
+        let second: Vec<String> = (0..5).map(|_| strategy.next_guess(&view)).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_random_candidate_strategy_plays_candidates() {
+        let mut strategy = RandomCandidateStrategy::new(solver());
+        strategy.new_game(7);
+        let guess_results = vec![GuessResult::from_pattern("spoon", "bbygg").unwrap()];
+        let view = GameView {
+            guess_results: &guess_results,
+            max_guesses: 6,
+            word_length: 5,
+            hard_mode: false,
+        };
+        assert_eq!("colon", strategy.next_guess(&view));
+    }
+
+    #[test]
+    fn test_solver_strategy_hard_mode() {
+        let words = ["bat", "bcd", "cat", "dat"];
+        let solver = Rc::new(Solver::new(words.iter().map(|w| w.to_string()).collect()));
+        let mut strategy = SolverStrategy::new(solver);
+        let guess_results = vec![GuessResult::from_pattern("fat", "bgg").unwrap()];
+        let mut view = GameView {
+            guess_results: &guess_results,
+            max_guesses: 6,
+            word_length: 3,
+            hard_mode: false,
+        };
+        // "bcd" splits the candidates, but drops the a and t revealed so far
+        assert_eq!("bcd", strategy.next_guess(&view));
+        view.hard_mode = true;
+        assert_eq!("bat", strategy.next_guess(&view));
+    }
+}
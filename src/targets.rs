@@ -0,0 +1,160 @@
+use crate::dictionary::Dictionary;
+use crate::difficulty::Difficulty;
+use crate::share;
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Picks the target words of a session of games.
+pub struct TargetPicker {
+    /// words a target may be picked from, sorted so that a seed always
+    /// picks the same targets
+    answers: Vec<String>,
+    rng: StdRng,
+}
+
+impl TargetPicker {
+    /// Pick targets from a list of answers, in a random order that only
+    /// depends on the seed, if one is provided.
+    /// panics if there are no answers.
+    pub fn new(mut answers: Vec<String>, seed: Option<u64>) -> TargetPicker {
+        if answers.is_empty() {
+            panic!("no answers to pick targets from");
+        }
+        answers.sort();
+        answers.dedup();
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        TargetPicker { answers, rng }
+    }
+
+    /// Next random target.
+    pub fn pick(&mut self) -> String {
+        self.answers.choose(&mut self.rng).unwrap().to_string()
+    }
+
+    /// Target of the daily puzzle, the same for everyone with the same
+    /// answers on that date.
+    pub fn daily(&self, date: NaiveDate) -> String {
+        let mut rng = StdRng::seed_from_u64(share::puzzle_number(date) as u64);
+        self.answers.choose(&mut rng).unwrap().to_string()
+    }
+
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+}
+
+/// Answers of the provided difficulty, among all the answers.
+/// Unrated answers are left out, so the list may be empty.
+pub fn with_difficulty(dict: &Dictionary, answers: &[String], level: Difficulty) -> Vec<String> {
+    answers
+        .iter()
+        .filter(|word| dict.difficulty(word).map(|rating| rating.level()) == Some(level))
+        .cloned()
+        .collect()
+}
+
+/// Load a list of answers with one word per line, each of which must be in
+/// the dictionary. Blank lines are skipped.
+pub fn load_answers<P: AsRef<Path>>(path: P, dict: &Dictionary) -> io::Result<Vec<String>> {
+    let mut answers = vec![];
+    for line in fs::read_to_string(path)?.lines() {
+        let word = line.trim();
+        if word.is_empty() {
+            continue;
+        }
+        if !dict.contains(&word.to_string()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{word} is not in the dictionary"),
+            ));
+        }
+        answers.push(word.to_string());
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::DifficultyRating;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn test_seeded_picks_repeat() {
+        let answers = words(&["cat", "bat", "rat", "mat", "sat", "hat"]);
+        let picks = |answers: Vec<String>| {
+            let mut picker = TargetPicker::new(answers, Some(7));
+            (0..10).map(|_| picker.pick()).collect::<Vec<_>>()
+        };
+        let first = picks(answers.clone());
+        // the order of the answers does not matter
+        let mut reversed = answers.clone();
+        reversed.reverse();
+        assert_eq!(first, picks(reversed));
+        assert!(first.iter().all(|word| answers.contains(word)));
+    }
+
+    #[test]
+    fn test_daily() {
+        let picker = TargetPicker::new(words(&["cat", "bat", "rat", "mat"]), None);
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let other = TargetPicker::new(words(&["mat", "rat", "bat", "cat"]), Some(3));
+        assert_eq!(picker.daily(date), other.daily(date));
+        assert!(picker.answers().contains(&picker.daily(date)));
+    }
+
+    #[test]
+    #[should_panic(expected = "no answers to pick targets from")]
+    fn test_no_answers() {
+        TargetPicker::new(vec![], None);
+    }
+
+    #[test]
+    fn test_with_difficulty() {
+        let mut dict = Dictionary::new(3);
+        dict.add_word_str("cat");
+        dict.add_word_str("bat");
+        dict.add_word_str("rat");
+        let easy = DifficultyRating {
+            solver_guesses: 2,
+            human_guesses: 3,
+            trap_group: 1,
+        };
+        dict.set_difficulty("cat", easy);
+        dict.set_difficulty("rat", easy);
+        assert_eq!(
+            words(&["cat"]),
+            with_difficulty(&dict, &words(&["cat", "bat"]), Difficulty::Easy)
+        );
+        assert!(with_difficulty(&dict, &dict.sorted_words(), Difficulty::Expert).is_empty());
+    }
+
+    #[test]
+    fn test_load_answers() {
+        let mut dict = Dictionary::new(3);
+        dict.add_word_str("cat");
+        dict.add_word_str("bat");
+        let path = std::env::temp_dir().join(format!("wordle-star-answers-{}", std::process::id()));
+
+        fs::write(&path, "cat\n\n bat \n").unwrap();
+        assert_eq!(words(&["cat", "bat"]), load_answers(&path, &dict).unwrap());
+
+        fs::write(&path, "cat\ndog\n").unwrap();
+        let err = load_answers(&path, &dict).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!("dog is not in the dictionary", err.to_string());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use crate::dictionary::Dictionary;
+use crate::game::Game;
+use crate::game::GameState;
+use crate::strategy::Strategy;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+
+/// Invalid words a strategy may submit in one game before it forfeits the
+/// game, so that a broken bot cannot stall the tournament.
+pub const MAX_INVALID_GUESSES: usize = 20;
+
+/// Plays several strategies against the same target words with the same
+/// seeds, using the real `Game` rules, and ranks them.
+pub struct Tournament {
+    dict: Dictionary,
+    targets: Vec<String>,
+    max_guesses: usize,
+    seed: u64,
+    hard_mode: bool,
+}
+
+/// Results of one strategy over all the games of a tournament.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub played: usize,
+    pub wins: usize,
+
+    /// guesses used in the games that were won
+    pub winning_guesses: usize,
+
+    /// words submitted that were not in the dictionary, or broke the rules
+    /// of hard mode
+    pub invalid_guesses: usize,
+
+    /// number of games won in exactly n guesses, at index n - 1
+    pub distribution: Vec<usize>,
+}
+
+impl Standing {
+    fn new(name: String, max_guesses: usize) -> Standing {
+        Standing {
+            name,
+            played: 0,
+            wins: 0,
+            winning_guesses: 0,
+            invalid_guesses: 0,
+            distribution: vec![0; max_guesses],
+        }
+    }
+
+    /// Fraction of games won, between 0 and 1.
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.played as f64
+    }
+
+    /// Average number of guesses in the games that were won.
+    pub fn average_guesses(&self) -> Option<f64> {
+        if self.wins == 0 {
+            return None;
+        }
+        Some(self.winning_guesses as f64 / self.wins as f64)
+    }
+
+    /// Leaderboard order: more wins first, then fewer guesses per win.
+    fn rank(&self, other: &Standing) -> Ordering {
+        other.wins.cmp(&self.wins).then_with(|| {
+            let guesses = self.average_guesses().unwrap_or(f64::MAX);
+            let other_guesses = other.average_guesses().unwrap_or(f64::MAX);
+            guesses.total_cmp(&other_guesses)
+        })
+    }
+}
+
+impl Tournament {
+    /// Create a tournament over the provided target words, which must all be
+    /// in the dictionary. The seed decides the seed of each game.
+    pub fn new(
+        dict: Dictionary,
+        targets: Vec<String>,
+        max_guesses: usize,
+        seed: u64,
+    ) -> Tournament {
+        Tournament {
+            dict,
+            targets,
+            max_guesses,
+            seed,
+            hard_mode: false,
+        }
+    }
+
+    /// Play every game in hard mode, see `Game::set_hard_mode`.
+    pub fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    /// Seeds of each game, one per target word.
+    fn game_seeds(&self) -> Vec<u64> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        self.targets.iter().map(|_| rng.gen()).collect()
+    }
+
+    /// Play every strategy against every target word.
+    /// Returns the leaderboard, best strategy first.
+    pub fn run(&self, strategies: &mut [Box<dyn Strategy>]) -> Vec<Standing> {
+        let seeds = self.game_seeds();
+        let mut standings = vec![];
+        for strategy in strategies.iter_mut() {
+            let mut standing = Standing::new(strategy.name(), self.max_guesses);
+            for (target, seed) in self.targets.iter().zip(seeds.iter()) {
+                self.play(strategy.as_mut(), target, *seed, &mut standing);
+            }
+            standings.push(standing);
+        }
+        standings.sort_by(|a, b| a.rank(b));
+        standings
+    }
+
+    /// Play one game and record its result in the standing.
+    fn play(&self, strategy: &mut dyn Strategy, target: &str, seed: u64, standing: &mut Standing) {
+        let mut game = Game::new(self.dict.clone(), target, self.max_guesses);
+        game.set_hard_mode(self.hard_mode);
+        strategy.new_game(seed);
+
+        let mut invalid_guesses = 0;
+        while game.state == GameState::Playing && invalid_guesses < MAX_INVALID_GUESSES {
+            let guess = strategy.next_guess(&game.view());
+            if game.guess_word(&guess).is_none() {
+                invalid_guesses += 1;
+            }
+        }
+
+        standing.played += 1;
+        standing.invalid_guesses += invalid_guesses;
+        if game.state == GameState::Win {
+            let guesses = game.guess_results.len();
+            standing.wins += 1;
+            standing.winning_guesses += guesses;
+            standing.distribution[guesses - 1] += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameView;
+    use crate::solver::Solver;
+    use crate::strategy::{RandomCandidateStrategy, SolverStrategy};
+    use std::rc::Rc;
+
+    fn big_dict() -> Dictionary {
+        let mut dict = Dictionary::new(5);
+        for word in [
+            "clone", "colon", "other", "ovolo", "potoo", "siena", "spoon",
+        ] {
+            dict.add_word_str(word);
+        }
+        dict
+    }
+
+    /// Always guesses a word that is not in the dictionary.
+    struct InvalidStrategy;
+
+    impl Strategy for InvalidStrategy {
+        fn name(&self) -> String {
+            "invalid".to_string()
+        }
+
+        fn next_guess(&mut self, _view: &GameView) -> String {
+            "zzzzz".to_string()
+        }
+    }
+
+    /// Always guesses the same word.
+    struct FixedStrategy(&'static str);
+
+    impl Strategy for FixedStrategy {
+        fn name(&self) -> String {
+            format!("fixed-{}", self.0)
+        }
+
+        fn next_guess(&mut self, _view: &GameView) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let dict = big_dict();
+        let solver = Rc::new(Solver::new(dict.sorted_words()));
+        let tournament = Tournament::new(dict.clone(), dict.sorted_words(), 6, 1);
+
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(InvalidStrategy),
+            Box::new(FixedStrategy("spoon")),
+            Box::new(SolverStrategy::new(solver.clone())),
+            Box::new(RandomCandidateStrategy::new(solver)),
+        ];
+        let standings = tournament.run(&mut strategies);
+
+        assert_eq!(4, standings.len());
+        assert!(standings.iter().all(|s| s.played == 7));
+        // a solver always wins with so few words
+        assert_eq!(7, standings[0].wins);
+        assert!(standings[0].name == "solver" || standings[0].name == "random-candidate");
+
+        let fixed = &standings[2];
+        assert_eq!("fixed-spoon", fixed.name);
+        assert_eq!(1, fixed.wins);
+        assert_eq!(vec![1, 0, 0, 0, 0, 0], fixed.distribution);
+        assert_eq!(Some(1.0), fixed.average_guesses());
+
+        let invalid = &standings[3];
+        assert_eq!("invalid", invalid.name);
+        assert_eq!(0, invalid.wins);
+        assert_eq!(0.0, invalid.win_rate());
+        assert_eq!(7 * MAX_INVALID_GUESSES, invalid.invalid_guesses);
+    }
+
+    #[test]
+    fn test_same_seeds_same_results() {
+        let dict = big_dict();
+        let solver = Rc::new(Solver::new(dict.sorted_words()));
+        let tournament = Tournament::new(dict.clone(), dict.sorted_words(), 6, 99);
+
+        let mut strategies: Vec<Box<dyn Strategy>> =
+            vec![Box::new(RandomCandidateStrategy::new(solver))];
+        let first = tournament.run(&mut strategies);
+        let second = tournament.run(&mut strategies);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        let dict = big_dict();
+        let solver = Rc::new(Solver::new(dict.sorted_words()));
+        let mut tournament = Tournament::new(dict.clone(), dict.sorted_words(), 6, 1);
+        tournament.set_hard_mode(true);
+
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(SolverStrategy::new(solver.clone())),
+            Box::new(RandomCandidateStrategy::new(solver)),
+        ];
+        for standing in tournament.run(&mut strategies) {
+            assert_eq!(0, standing.invalid_guesses, "{}", standing.name);
+            assert_eq!(standing.played, standing.wins, "{}", standing.name);
+        }
+    }
+}